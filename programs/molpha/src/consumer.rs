@@ -0,0 +1,137 @@
+//! Helpers for consumer programs reading Molpha feeds through CPI.
+//! Available with the `cpi` feature.
+
+use crate::state::{AnswerV2, AverageAnswer, HistoricalAnswer};
+use anchor_lang::prelude::*;
+
+/// Reads the latest answer of `feed` through the `read_answer` instruction.
+/// Fails with `FeedError::StaleAnswer`, `InsufficientSigners`, `FeedPaused`,
+/// `FeedRetired`, `SubscriptionExpired` or `NoAnswer` when the answer should
+/// not be used.
+pub fn read_answer<'info>(
+    molpha_program: AccountInfo<'info>,
+    feed: AccountInfo<'info>,
+    max_age_seconds: u64,
+    min_signers: u8,
+) -> Result<AnswerV2> {
+    let cpi_context = CpiContext::new(molpha_program, crate::cpi::accounts::ReadAnswer { feed });
+    Ok(crate::cpi::read_answer(cpi_context, max_age_seconds, min_signers)?.get())
+}
+
+/// Reads the latest answer of a `DerivedFeed`, with the same guards as
+/// `read_answer`.
+pub fn read_derived_answer<'info>(
+    molpha_program: AccountInfo<'info>,
+    derived_feed: AccountInfo<'info>,
+    max_age_seconds: u64,
+    min_signers: u8,
+) -> Result<AnswerV2> {
+    let cpi_context = CpiContext::new(
+        molpha_program,
+        crate::cpi::accounts::ReadDerivedAnswer { derived_feed },
+    );
+    Ok(crate::cpi::read_derived_answer(cpi_context, max_age_seconds, min_signers)?.get())
+}
+
+/// Reads slot `index` of a `FeedBundle`, with the same guards as
+/// `read_answer`.
+pub fn read_bundle_slot<'info>(
+    molpha_program: AccountInfo<'info>,
+    bundle: AccountInfo<'info>,
+    index: u16,
+    max_age_seconds: u64,
+    min_signers: u8,
+) -> Result<AnswerV2> {
+    let cpi_context = CpiContext::new(molpha_program, crate::cpi::accounts::ReadBundleSlot { bundle });
+    Ok(crate::cpi::read_bundle_slot(cpi_context, index, max_age_seconds, min_signers)?.get())
+}
+
+/// Reads the latest answer of a personal `feed` with restricted readers. The
+/// calling program must be in the feed's `FeedReaders` account, `readers`.
+pub fn read_answer_gated<'info>(
+    molpha_program: AccountInfo<'info>,
+    feed: AccountInfo<'info>,
+    readers: AccountInfo<'info>,
+    instructions: AccountInfo<'info>,
+    max_age_seconds: u64,
+    min_signers: u8,
+) -> Result<AnswerV2> {
+    let cpi_context = CpiContext::new(
+        molpha_program,
+        crate::cpi::accounts::ReadAnswerGated { feed, readers, instructions },
+    );
+    Ok(crate::cpi::read_answer_gated(cpi_context, max_age_seconds, min_signers)?.get())
+}
+
+/// Reads the latest answer of a public `feed` for `subscriber`, which must sign
+/// (a consumer program signs with its PDA through `invoke_signed`). Fails like
+/// `read_answer`, and with `FeedError::SubscriptionExpired` when the
+/// subscriber's subscription has run out.
+pub fn read_subscribed_answer<'info>(
+    molpha_program: AccountInfo<'info>,
+    feed: AccountInfo<'info>,
+    subscription: AccountInfo<'info>,
+    subscriber: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    max_age_seconds: u64,
+    min_signers: u8,
+) -> Result<AnswerV2> {
+    let cpi_context = CpiContext::new_with_signer(
+        molpha_program,
+        crate::cpi::accounts::ReadSubscribedAnswer { feed, subscription, subscriber },
+        signer_seeds,
+    );
+    Ok(crate::cpi::read_subscribed_answer(cpi_context, max_age_seconds, min_signers)?.get())
+}
+
+/// Reads the answer of `feed` in effect at `timestamp` from its history
+/// account. Fails with `FeedError::AnswerNotFound` if the history does not
+/// cover `timestamp`. For feeds with restricted readers, pass the feed's
+/// `FeedReaders` and the instructions sysvar, as for `read_answer_gated`.
+pub fn get_answer_at<'info>(
+    molpha_program: AccountInfo<'info>,
+    feed: AccountInfo<'info>,
+    history: AccountInfo<'info>,
+    readers: Option<AccountInfo<'info>>,
+    instructions: Option<AccountInfo<'info>>,
+    timestamp: i64,
+) -> Result<HistoricalAnswer> {
+    let cpi_context = CpiContext::new(
+        molpha_program,
+        crate::cpi::accounts::ReadHistory { feed, history, readers, instructions },
+    );
+    Ok(crate::cpi::get_answer_at(cpi_context, timestamp)?.get())
+}
+
+/// Reads the answer of round `round_id` of `feed` from its history account.
+pub fn get_round<'info>(
+    molpha_program: AccountInfo<'info>,
+    feed: AccountInfo<'info>,
+    history: AccountInfo<'info>,
+    readers: Option<AccountInfo<'info>>,
+    instructions: Option<AccountInfo<'info>>,
+    round_id: u64,
+) -> Result<HistoricalAnswer> {
+    let cpi_context = CpiContext::new(
+        molpha_program,
+        crate::cpi::accounts::ReadHistory { feed, history, readers, instructions },
+    );
+    Ok(crate::cpi::get_round(cpi_context, round_id)?.get())
+}
+
+/// Reads the TWAP over the last `window_seconds` and the EMA of `feed`. Fails
+/// with `FeedError::WindowNotCovered` if the history is too short for the window.
+pub fn get_twap<'info>(
+    molpha_program: AccountInfo<'info>,
+    feed: AccountInfo<'info>,
+    history: AccountInfo<'info>,
+    readers: Option<AccountInfo<'info>>,
+    instructions: Option<AccountInfo<'info>>,
+    window_seconds: u64,
+) -> Result<AverageAnswer> {
+    let cpi_context = CpiContext::new(
+        molpha_program,
+        crate::cpi::accounts::ReadHistory { feed, history, readers, instructions },
+    );
+    Ok(crate::cpi::get_twap(cpi_context, window_seconds)?.get())
+}
@@ -16,6 +16,14 @@ pub enum NodeRegistryError {
     InvalidEd25519Instruction,
 }
 
+#[error_code]
+pub enum ProtocolError {
+    #[msg("Invalid protocol configuration.")]
+    InvalidProtocolConfig,
+    #[msg("The protocol config already has the current layout.")]
+    ProtocolConfigUpToDate,
+}
+
 #[error_code]
 pub enum FeedError {
     #[msg("Invalid feed configuration.")]
@@ -44,6 +52,90 @@ pub enum FeedError {
     MinimumExtensionTime,
     #[msg("Insufficient priority fee budget.")]
     InsufficientPriorityFeeBudget,
+    #[msg("The minimum interval between updates has not elapsed.")]
+    UpdateTooFrequent,
+    #[msg("The answer meets neither the deviation threshold nor the heartbeat.")]
+    UpdateConditionsNotMet,
+    #[msg("The observation window ends before it starts.")]
+    InvalidObservationWindow,
+    #[msg("The account is not a feed in the legacy layout.")]
+    NotLegacyFeed,
+    #[msg("The answer value does not match the feed's value type.")]
+    InvalidValue,
+    #[msg("A batch must contain at least one answer.")]
+    EmptyBatch,
+    #[msg("The batch answers do not match the feed accounts passed.")]
+    BatchAccountsMismatch,
+    #[msg("The Merkle proof does not match the signed batch root.")]
+    InvalidMerkleProof,
+    #[msg("The caller is not allowed to read this feed.")]
+    ReaderNotAllowed,
+    #[msg("The feed is paused.")]
+    FeedPaused,
+    #[msg("The feed has no answer yet.")]
+    NoAnswer,
+    #[msg("The latest answer is older than the maximum age.")]
+    StaleAnswer,
+    #[msg("The latest answer has fewer signers than required.")]
+    InsufficientSigners,
+    #[msg("Another transmitter is scheduled for this feed and round.")]
+    NotDesignatedTransmitter,
+    #[msg("The history account does not belong to the feed.")]
+    InvalidHistoryAccount,
+    #[msg("Invalid history capacity.")]
+    InvalidHistoryCapacity,
+    #[msg("No answer in the history for the requested time or round.")]
+    AnswerNotFound,
+    #[msg("The history does not cover the requested window.")]
+    WindowNotCovered,
+    #[msg("The exponent changed within the requested window.")]
+    ExponentChanged,
+    #[msg("The signer is not the pending authority of the feed.")]
+    NotPendingAuthority,
+    #[msg("Too many readers.")]
+    TooManyReaders,
+    #[msg("The feed is retired.")]
+    FeedRetired,
+    #[msg("The feed cannot move to this status.")]
+    InvalidStatusTransition,
+    #[msg("The signer is neither the feed authority nor the protocol admin.")]
+    Unauthorized,
+    #[msg("Auto-renew is not enabled for this feed.")]
+    AutoRenewDisabled,
+    #[msg("The subscription is not due for renewal yet.")]
+    RenewalNotDue,
+    #[msg("The reader list does not belong to the feed.")]
+    InvalidReadersAccount,
+    #[msg("Arithmetic overflow.")]
+    ArithmeticOverflow,
+    #[msg("Subscribers still have access to the feed.")]
+    ActiveSubscriptions,
+    #[msg("Feed is already at its creator and name address.")]
+    FeedAlreadyMigrated,
+}
+
+#[error_code]
+pub enum RoundError {
+    #[msg("Round ID does not follow the current reporting round.")]
+    InvalidRoundId,
+    #[msg("The current reporting round is still open.")]
+    RoundInProgress,
+    #[msg("The round has been superseded by a newer round.")]
+    RoundSuperseded,
+    #[msg("The round has timed out.")]
+    RoundTimedOut,
+    #[msg("The round has already been finalized.")]
+    RoundAlreadyFinalized,
+    #[msg("The node has already submitted to this round.")]
+    DuplicateSubmission,
+    #[msg("The observation exponent differs from the round exponent.")]
+    ExponentMismatch,
+    #[msg("Observation value differs from the round's value for a non-numeric feed.")]
+    ValueMismatch,
+    #[msg("The round is still accepting submissions.")]
+    RoundStillOpen,
+    #[msg("The round has no room for more submissions.")]
+    RoundFull,
 }
 
 #[error_code]
@@ -55,3 +147,31 @@ pub enum DataSourceError {
     #[msg("Invalid data source type.")]
     InvalidDataSourceType,
 }
+
+#[error_code]
+pub enum DerivedFeedError {
+    #[msg("The sources or weights do not fit the expression.")]
+    InvalidExpression,
+    #[msg("The source feeds passed do not match the derived feed.")]
+    SourcesMismatch,
+    #[msg("A source feed has no numeric value.")]
+    NonNumericSource,
+    #[msg("Division by zero.")]
+    DivisionByZero,
+    #[msg("Arithmetic overflow.")]
+    ArithmeticOverflow,
+}
+
+#[error_code]
+pub enum BundleError {
+    #[msg("Invalid bundle configuration.")]
+    InvalidBundleConfig,
+    #[msg("The bundle has no room for more slots.")]
+    TooManySlots,
+    #[msg("The report does not have one value per slot.")]
+    SlotCountMismatch,
+    #[msg("No slot at this index.")]
+    SlotNotFound,
+    #[msg("The latest report does not cover this slot.")]
+    SlotNotReported,
+}
@@ -1,4 +1,4 @@
-use crate::state::{DataSourceType, FeedType, Answer};
+use crate::state::{AnswerReport, AnswerV2, DataSourceType, DerivedExpression, FeedStatus, FeedType};
 use anchor_lang::prelude::*;
 
 #[event]
@@ -29,7 +29,140 @@ pub struct FeedCreated {
 #[event]
 pub struct AnswerPublished {
     pub feed: Pubkey,
-    pub answer: Answer,
+    pub answer: AnswerV2,
+    pub signatures_count: u8,
+    pub published_at: i64,
+}
+
+#[event]
+pub struct FeedClosed {
+    pub feed: Pubkey,
+    pub authority: Pubkey,
+    pub refund: u64,
+    pub closed_at: i64,
+}
+
+#[event]
+pub struct DerivedFeedCreated {
+    pub derived_feed: Pubkey,
+    pub authority: Pubkey,
+    pub expression: DerivedExpression,
+    pub sources: Vec<Pubkey>,
+    pub exponent: i32,
+    pub created_at: i64,
+}
+
+#[event]
+pub struct DerivedFeedRefreshed {
+    pub derived_feed: Pubkey,
+    pub answer: AnswerV2,
+}
+
+#[event]
+pub struct FeedBundleCreated {
+    pub bundle: Pubkey,
+    pub authority: Pubkey,
+    pub capacity: u16,
+    pub exponent: i32,
+    pub created_at: i64,
+}
+
+#[event]
+pub struct BundleSlotsAdded {
+    pub bundle: Pubkey,
+    pub names: Vec<String>,
+    pub slots_count: u16,
+}
+
+#[event]
+pub struct BundlePublished {
+    pub bundle: Pubkey,
+    pub round_id: u64,
+    pub slots_count: u16,
+    pub signatures_count: u8,
+    pub observed_to: i64,
+    pub published_at: i64,
+}
+
+#[event]
+pub struct FeedHistoryCreated {
+    pub feed: Pubkey,
+    pub history: Pubkey,
+    pub capacity: u32,
+    pub created_at: i64,
+}
+
+#[event]
+pub struct FeedHistoryResized {
+    pub feed: Pubkey,
+    pub history: Pubkey,
+    pub capacity: u32,
+    pub resized_at: i64,
+}
+
+#[event]
+pub struct FeedAuthorityTransferProposed {
+    pub feed: Pubkey,
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub proposed_at: i64,
+}
+
+#[event]
+pub struct FeedAuthorityTransferred {
+    pub feed: Pubkey,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub transferred_at: i64,
+}
+
+#[event]
+pub struct FeedReadersUpdated {
+    pub feed: Pubkey,
+    pub authority: Pubkey,
+    pub readers: Vec<Pubkey>,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct FeedStatusUpdated {
+    pub feed: Pubkey,
+    pub updated_by: Pubkey,
+    pub previous_status: FeedStatus,
+    pub status: FeedStatus,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct ReportVerified {
+    pub feed: Pubkey,
+    pub consumer: Pubkey,
+    pub answer: AnswerV2,
+    pub fee: u64,
+    pub verified_at: i64,
+}
+
+#[event]
+pub struct TransmitterReimbursed {
+    pub transmitter: Pubkey,
+    pub amount: u64,
+    pub reimbursed_at: i64,
+}
+
+#[event]
+pub struct AnswerRejectedOutlier {
+    pub feed: Pubkey,
+    pub answer: AnswerV2,
+    pub deviation_bps: u64,
+    pub signatures_count: u8,
+    pub required_signatures: u8,
+    pub rejected_at: i64,
+}
+
+#[event]
+pub struct BatchPublished {
+    pub root: [u8; 32],
+    pub feeds_count: u8,
     pub signatures_count: u8,
     pub published_at: i64,
 }
@@ -41,9 +174,58 @@ pub struct SubscriptionExtended {
     pub additional_duration: u64,
     pub additional_priority_budget: u64,
     pub new_due_time: i64,
+    pub settled_debt: u64,
     pub extended_at: i64,
 }
 
+#[event]
+pub struct SubscriptionLow {
+    pub feed: Pubkey,
+    pub remaining_time: i64,
+    pub remaining_priority_budget: u64,
+    pub emitted_at: i64,
+}
+
+#[event]
+pub struct SubscriptionInGrace {
+    pub feed: Pubkey,
+    pub due_time: i64,
+    pub grace_ends_at: i64,
+    pub debt: u64,
+    pub emitted_at: i64,
+}
+
+#[event]
+pub struct AutoRenewUpdated {
+    pub feed: Pubkey,
+    pub authority: Pubkey,
+    pub duration: u64,
+    pub priority_budget: u64,
+    pub window: u64,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct SubscriptionAutoRenewed {
+    pub feed: Pubkey,
+    pub cranker: Pubkey,
+    pub cost: u64,
+    pub tip: u64,
+    pub new_due_time: i64,
+    pub renewed_at: i64,
+}
+
+#[event]
+pub struct FeedSubscribed {
+    pub feed: Pubkey,
+    pub subscriber: Pubkey,
+    pub subscription: Pubkey,
+    pub duration: u64,
+    pub cost: u64,
+    pub new_due_time: i64,
+    pub subscribed_at: i64,
+}
+
 #[event]
 pub struct NodeAdded {
     pub node_registry: Pubkey,
@@ -85,6 +267,13 @@ pub struct ProtocolInitialized {
     pub initialized_at: i64,
 }
 
+#[event]
+pub struct ProtocolConfigUpdated {
+    pub protocol_config: Pubkey,
+    pub authority: Pubkey,
+    pub updated_at: i64,
+}
+
 #[event]
 pub struct FeedConfigUpdated {
     pub feed: Pubkey,
@@ -108,3 +297,62 @@ pub struct NodeRegistryInitialized {
     pub authority: Pubkey,
     pub initialized_at: i64,
 }
+
+#[event]
+pub struct RoundStarted {
+    pub feed: Pubkey,
+    pub round_id: u64,
+    pub started_by: Pubkey,
+    pub started_at: i64,
+}
+
+#[event]
+pub struct ObservationSubmitted {
+    pub feed: Pubkey,
+    pub round_id: u64,
+    pub node: Pubkey,
+    pub report: AnswerReport,
+    pub submissions_count: u8,
+    pub submitted_at: i64,
+}
+
+#[event]
+pub struct RoundFinalized {
+    pub feed: Pubkey,
+    pub round_id: u64,
+    pub answer: AnswerV2,
+    pub submissions_count: u8,
+    pub finalized_at: i64,
+}
+
+#[event]
+pub struct RoundSuperseded {
+    pub feed: Pubkey,
+    pub round_id: u64,
+    pub superseded_by: u64,
+    pub superseded_at: i64,
+}
+
+#[event]
+pub struct FeedAddressMigrated {
+    pub old_feed: Pubkey,
+    pub new_feed: Pubkey,
+    pub authority: Pubkey,
+    pub migrated_at: i64,
+}
+
+#[event]
+pub struct ProtocolConfigMigrated {
+    pub protocol_config: Pubkey,
+    pub migrated_by: Pubkey,
+    pub previous_space: u16,
+    pub migrated_at: i64,
+}
+
+#[event]
+pub struct FeedMigrated {
+    pub feed: Pubkey,
+    pub migrated_by: Pubkey,
+    pub history_len: u8,
+    pub migrated_at: i64,
+}
@@ -0,0 +1,177 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::error::FeedError;
+use crate::events::{AutoRenewUpdated, SubscriptionAutoRenewed};
+use crate::state::{AutoRenew, Feed, ProtocolConfig};
+
+/// Opts a feed into auto-renew, or out of it with a `duration` of 0. Each
+/// renewal extends the subscription by `duration` seconds and adds
+/// `priority_budget` to the allowance, once the due time is less than
+/// `window` seconds away. `window` must be shorter than `duration`, so that a
+/// renewal moves the due time out of the window and cannot repeat. Payments
+/// are pulled from the authority's token account, which must approve the
+/// protocol config as delegate; the approved amount caps what auto-renew can
+/// spend. The settings are bound to that authority: after a transfer, the new
+/// authority has to opt in again before renewals resume.
+pub fn set_auto_renew(
+    ctx: Context<SetAutoRenew>,
+    duration: u64,
+    priority_budget: u64,
+    window: u64,
+) -> Result<()> {
+    require!(
+        duration == 0 || duration >= 86400, // At least 1 day
+        FeedError::MinimumExtensionTime
+    );
+    require!(duration == 0 || window < duration, FeedError::InvalidFeedConfig);
+
+    let auto_renew = &mut ctx.accounts.auto_renew;
+    auto_renew.feed = ctx.accounts.feed.key();
+    auto_renew.payer = ctx.accounts.authority.key();
+    auto_renew.duration = duration;
+    auto_renew.priority_budget = priority_budget;
+    auto_renew.window = window;
+    auto_renew.bump = ctx.bumps.auto_renew;
+
+    emit!(AutoRenewUpdated {
+        feed: ctx.accounts.feed.key(),
+        authority: ctx.accounts.authority.key(),
+        duration,
+        priority_budget,
+        window,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Renews a feed with auto-renew that is due for renewal. Permissionless: the
+/// cost and a tip of `ProtocolConfig::crank_tip_bps` of it are pulled from the
+/// authority's token account with the protocol config as delegate, and the
+/// tip goes to the cranker.
+pub fn crank_renew(ctx: Context<CrankRenew>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let (cost, new_due_time) = {
+        let mut feed = ctx.accounts.feed.load_mut()?;
+        let auto_renew = &ctx.accounts.auto_renew;
+        require!(auto_renew.is_enabled(), FeedError::AutoRenewDisabled);
+        require!(feed.status().accepts_answers(), FeedError::FeedRetired);
+        require!(auto_renew.is_renewal_due(&feed, now), FeedError::RenewalNotDue);
+
+        let (duration, priority_budget) = (auto_renew.duration, auto_renew.priority_budget);
+        let cost = feed.extend_subscription(now, duration, priority_budget)?;
+        (cost, feed.subscription_due_time)
+    };
+    let tip = (cost as u128 * ctx.accounts.protocol_config.crank_tip_bps as u128 / 10_000) as u64;
+
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        ProtocolConfig::SEED_PREFIX,
+        &[ctx.accounts.protocol_config.bump],
+    ]];
+    let decimals = ctx.accounts.underlying_token.decimals;
+    for (to, amount) in [
+        (ctx.accounts.program_token_account.to_account_info(), cost),
+        (ctx.accounts.cranker_token_account.to_account_info(), tip),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.owner_token_account.to_account_info(),
+            to,
+            authority: ctx.accounts.protocol_config.to_account_info(),
+            mint: ctx.accounts.underlying_token.to_account_info(),
+        };
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        transfer_checked(cpi_context, amount, decimals)?;
+    }
+
+    emit!(SubscriptionAutoRenewed {
+        feed: ctx.accounts.feed.key(),
+        cranker: ctx.accounts.cranker.key(),
+        cost,
+        tip,
+        new_due_time,
+        renewed_at: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetAutoRenew<'info> {
+    #[account(has_one = authority)]
+    pub feed: AccountLoader<'info, Feed>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = AutoRenew::SPACE,
+        seeds = [AutoRenew::SEED_PREFIX, feed.key().as_ref()],
+        bump
+    )]
+    pub auto_renew: Account<'info, AutoRenew>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CrankRenew<'info> {
+    #[account(mut)]
+    pub feed: AccountLoader<'info, Feed>,
+
+    #[account(
+        seeds = [AutoRenew::SEED_PREFIX, feed.key().as_ref()],
+        bump = auto_renew.bump,
+    )]
+    pub auto_renew: Account<'info, AutoRenew>,
+
+    pub cranker: Signer<'info>,
+
+    /// Feed authority's token account, delegated to the protocol config. The
+    /// authority must be the one that configured auto-renew.
+    #[account(
+        mut,
+        token::mint = underlying_token,
+        constraint = owner_token_account.owner == feed.load()?.authority @ FeedError::Unauthorized,
+        constraint = owner_token_account.owner == auto_renew.payer @ FeedError::Unauthorized,
+    )]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token account receiving the cranker's tip
+    #[account(
+        mut,
+        token::mint = underlying_token,
+    )]
+    pub cranker_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Program's associated token account to receive tokens
+    #[account(
+        mut,
+        associated_token::mint = underlying_token,
+        associated_token::authority = protocol_config,
+    )]
+    pub program_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// The underlying token mint
+    #[account(address = protocol_config.underlying_token)]
+    pub underlying_token: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
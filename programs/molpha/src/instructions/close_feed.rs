@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked},
+};
+
+use crate::error::FeedError;
+use crate::events::FeedClosed;
+use crate::state::{AutoRenew, Feed, FeedHistory, FeedReaders, FeedSuccessor, ProtocolConfig};
+use crate::utils::close_if_initialized;
+
+/// Closes a feed and its history account, returning their rent to the
+/// authority. The feed's reader list, successor record and auto-renew
+/// settings are closed with it when they exist, so they cannot carry over
+/// to a feed recreated at the same address. The unused part of the
+/// authority's subscription and of the priority fee allowance, less any
+/// grace debt still owed, is refunded from the program token account. Fails
+/// while a subscriber's `Subscription` is running; subscriber payments are
+/// tracked apart from the authority's time, so they are never refunded.
+pub fn close_feed(ctx: Context<CloseFeed>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let refund = {
+        let feed = ctx.accounts.feed.load()?;
+        // A passed history is checked against the feed's by its constraint
+        require!(
+            feed.has_history() == ctx.accounts.history.is_some(),
+            FeedError::InvalidHistoryAccount
+        );
+        require!(!feed.has_active_subscribers(now), FeedError::ActiveSubscriptions);
+        feed.refundable_amount(now)
+    };
+
+    if refund > 0 {
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            ProtocolConfig::SEED_PREFIX,
+            &[ctx.accounts.protocol_config.bump],
+        ]];
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.program_token_account.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.protocol_config.to_account_info(),
+            mint: ctx.accounts.underlying_token.to_account_info(),
+        };
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        transfer_checked(cpi_context, refund, ctx.accounts.underlying_token.decimals)?;
+    }
+
+    let authority = ctx.accounts.authority.to_account_info();
+    close_if_initialized(&ctx.accounts.readers, &authority)?;
+    close_if_initialized(&ctx.accounts.successor, &authority)?;
+    close_if_initialized(&ctx.accounts.auto_renew, &authority)?;
+
+    emit!(FeedClosed {
+        feed: ctx.accounts.feed.key(),
+        authority: ctx.accounts.authority.key(),
+        refund,
+        closed_at: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseFeed<'info> {
+    #[account(
+        mut,
+        has_one = authority,
+        close = authority
+    )]
+    pub feed: AccountLoader<'info, Feed>,
+
+    /// The feed's history account, required when it has one and rejected otherwise
+    #[account(
+        mut,
+        close = authority,
+        constraint = history.key() == feed.load()?.history @ FeedError::InvalidHistoryAccount,
+    )]
+    pub history: Option<AccountLoader<'info, FeedHistory>>,
+
+    /// CHECK: The feed's `FeedReaders` address, closed in the handler if it exists
+    #[account(
+        mut,
+        seeds = [FeedReaders::SEED_PREFIX, feed.key().as_ref()],
+        bump,
+    )]
+    pub readers: UncheckedAccount<'info>,
+
+    /// CHECK: The feed's `FeedSuccessor` address, closed in the handler if it exists
+    #[account(
+        mut,
+        seeds = [FeedSuccessor::SEED_PREFIX, feed.key().as_ref()],
+        bump,
+    )]
+    pub successor: UncheckedAccount<'info>,
+
+    /// CHECK: The feed's `AutoRenew` address, closed in the handler if it exists
+    #[account(
+        mut,
+        seeds = [AutoRenew::SEED_PREFIX, feed.key().as_ref()],
+        bump,
+    )]
+    pub auto_renew: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// User's associated token account receiving the refund
+    #[account(
+        mut,
+        associated_token::mint = underlying_token,
+        associated_token::authority = authority,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Program's associated token account paying the refund
+    #[account(
+        mut,
+        associated_token::mint = underlying_token,
+        associated_token::authority = protocol_config,
+    )]
+    pub program_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// The underlying token mint
+    #[account(address = protocol_config.underlying_token)]
+    pub underlying_token: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
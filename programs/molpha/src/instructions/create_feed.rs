@@ -7,7 +7,7 @@ use anchor_spl::{
 use crate::error::FeedError;
 use crate::events::{FeedCreated};
 use crate::state::{
-    DataSource, DataSourceType, Feed, FeedType, ProtocolConfig, MAX_HISTORY,
+    DataSource, DataSourceType, Feed, FeedType, ProtocolConfig, UpdatePolicy, ValueType,
 };
 use crate::utils::pricing::*;
 
@@ -22,6 +22,8 @@ pub fn create_feed(
         FeedError::InvalidFeedConfig
     );
     require!(!params.ipfs_cid.is_empty(), FeedError::InvalidFeedConfig);
+    require!(params.update_policy.is_valid(), FeedError::InvalidFeedConfig);
+    require!(params.frequency > 0, FeedError::InvalidFeedConfig);
     require!(
         subscription_duration_seconds >= 86400,
         FeedError::MinimumSubscriptionTime
@@ -40,29 +42,33 @@ pub fn create_feed(
     let data_source = &mut ctx.accounts.data_source;
 
     // Initialize feed with basic data
-    let feed = &mut ctx.accounts.feed;
-    feed.name = params.name;
+    let feed = &mut ctx.accounts.feed.load_init()?;
+    feed.set_name(&params.name)?;
     feed.authority = ctx.accounts.authority.key();
-    feed.feed_type = params.feed_type;
+    feed.creator = ctx.accounts.authority.key();
+    feed.feed_type = params.feed_type.to_u8();
+    feed.value_type = params.value_type.to_u8();
     feed.min_signatures_threshold = params.min_signatures_threshold;
     feed.frequency = params.frequency;
-    feed.ipfs_cid = params.ipfs_cid;
+    feed.set_update_policy(&params.update_policy);
+    feed.set_ipfs_cid(&params.ipfs_cid)?;
     feed.job_id = params.job_id;
     feed.data_source = data_source.key();
-    feed.answer_history = Vec::with_capacity(MAX_HISTORY);
     feed.created_at = now;
     feed.bump = ctx.bumps.feed;
     // Calculate subscription pricing (like PricingHelper.calculatePrice)
     let price_per_second_scaled = calculate_price_per_second_scaled(feed, config)?;
 
+    feed.price_per_second_scaled = price_per_second_scaled;
+
     // Calculate total subscription cost
-    let base_subscription_cost =
-        (price_per_second_scaled * subscription_duration_seconds) / ProtocolConfig::SCALAR;
-    let total_cost = base_subscription_cost + priority_fee_budget;
+    let base_subscription_cost = feed.subscription_cost(subscription_duration_seconds)?;
+    let total_cost = base_subscription_cost
+        .checked_add(priority_fee_budget)
+        .ok_or(FeedError::ArithmeticOverflow)?;
 
     // Initialize subscription data
     feed.subscription_due_time = now + subscription_duration_seconds as i64;
-    feed.price_per_second_scaled = price_per_second_scaled;
     feed.priority_fee_allowance = priority_fee_budget;
     feed.consumed_priority_fees = 0;
 
@@ -83,10 +89,10 @@ pub fn create_feed(
     emit!(FeedCreated {
         id: feed.job_id,
         authority: feed.authority,
-        feed_type: feed.feed_type,
+        feed_type: params.feed_type,
         min_signatures_threshold: feed.min_signatures_threshold,
         frequency: feed.frequency,
-        ipfs_cid: feed.ipfs_cid.clone(),
+        ipfs_cid: params.ipfs_cid,
         data_source: feed.data_source,
         created_at: now,
         subscription_due_time: feed.subscription_due_time,
@@ -117,14 +123,10 @@ pub struct CreateFeed<'info> {
             Feed::SEED_PREFIX,
             authority.key().as_ref(),
             params.name.as_bytes().as_ref(),
-            params.feed_type.to_seed().as_ref(),
-            params.min_signatures_threshold.to_le_bytes().as_ref(),
-            params.frequency.to_le_bytes().as_ref(),
-            params.job_id.as_ref(),
         ],
         bump
     )]
-    pub feed: Account<'info, Feed>,
+    pub feed: AccountLoader<'info, Feed>,
 
     /// CHECK: This account is verified to exist and be accessible
     #[account(
@@ -164,6 +166,7 @@ pub struct CreateFeed<'info> {
     pub program_token_account: InterfaceAccount<'info, TokenAccount>,
     
     /// The underlying token mint
+    #[account(address = protocol_config.underlying_token)]
     pub underlying_token: InterfaceAccount<'info, Mint>,
     
     pub system_program: Program<'info, System>,
@@ -176,7 +179,9 @@ pub struct CreateFeedParams {
     pub name: String,
     pub job_id: [u8; 32],
     pub feed_type: FeedType,
+    pub value_type: ValueType,
     pub min_signatures_threshold: u8,
     pub frequency: u64,
+    pub update_policy: UpdatePolicy,
     pub ipfs_cid: String,
 }
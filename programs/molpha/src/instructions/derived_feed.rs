@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+
+use crate::error::{DerivedFeedError, FeedError};
+use crate::events::{DerivedFeedCreated, DerivedFeedRefreshed};
+use crate::state::{encode_i128, AnswerV2, DerivedExpression, DerivedFeed, Feed, MAX_NAME_LEN};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateDerivedFeedParams {
+    pub name: String,
+    pub expression: DerivedExpression,
+    pub sources: Vec<Pubkey>,
+    pub weights: Vec<i64>,
+    pub exponent: i32,
+    pub max_staleness: u64,
+}
+
+pub fn create_derived_feed(
+    ctx: Context<CreateDerivedFeed>,
+    params: CreateDerivedFeedParams,
+) -> Result<()> {
+    require!(
+        !params.name.is_empty() && params.name.len() <= MAX_NAME_LEN,
+        FeedError::InvalidFeedConfig
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let derived_feed = &mut ctx.accounts.derived_feed;
+    derived_feed.authority = ctx.accounts.authority.key();
+    derived_feed.name = params.name;
+    derived_feed.expression = params.expression;
+    derived_feed.sources = params.sources;
+    derived_feed.weights = params.weights;
+    derived_feed.exponent = params.exponent;
+    derived_feed.max_staleness = params.max_staleness;
+    derived_feed.created_at = now;
+    derived_feed.bump = ctx.bumps.derived_feed;
+    require!(derived_feed.is_valid(), DerivedFeedError::InvalidExpression);
+
+    emit!(DerivedFeedCreated {
+        derived_feed: derived_feed.key(),
+        authority: derived_feed.authority,
+        expression: derived_feed.expression,
+        sources: derived_feed.sources.clone(),
+        exponent: derived_feed.exponent,
+        created_at: now,
+    });
+
+    Ok(())
+}
+
+/// Recomputes a derived feed from the latest answers of its sources, passed
+/// as remaining accounts in the order of `DerivedFeed::sources`. Each source
+/// must be readable (see `Feed::read_latest_answer`) with an answer at most
+/// `max_staleness` old. The effective timestamp, that of the oldest source
+/// answer, never goes backwards. Anyone can call it.
+pub fn refresh_derived<'info>(ctx: Context<'_, '_, 'info, 'info, RefreshDerived<'info>>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let derived_feed = &mut ctx.accounts.derived_feed;
+    require!(
+        ctx.remaining_accounts.len() == derived_feed.sources.len(),
+        DerivedFeedError::SourcesMismatch
+    );
+
+    let mut values = Vec::with_capacity(derived_feed.sources.len());
+    let mut answer = AnswerV2 {
+        round_id: derived_feed.latest_answer.round_id + 1,
+        exponent: derived_feed.exponent,
+        observed_from: i64::MAX,
+        observed_to: i64::MAX,
+        signer_count: u8::MAX,
+        ..Default::default()
+    };
+    for (source_info, source_key) in ctx.remaining_accounts.iter().zip(&derived_feed.sources) {
+        require_keys_eq!(source_info.key(), *source_key, DerivedFeedError::SourcesMismatch);
+        let source = AccountLoader::<Feed>::try_from(source_info)?;
+        let source = source.load()?;
+        require!(!source.has_restricted_readers(), FeedError::ReaderNotAllowed);
+
+        let latest = source.read_latest_answer(now, derived_feed.max_staleness, 0)?;
+        let value = source
+            .value_type()
+            .as_i128(&latest.value)
+            .ok_or(error!(DerivedFeedError::NonNumericSource))?;
+        values.push((value, latest.exponent));
+
+        answer.observed_from = answer.observed_from.min(latest.observed_from);
+        answer.observed_to = answer.observed_to.min(latest.observed_to);
+        answer.signer_count = answer.signer_count.min(latest.signer_count);
+    }
+    require!(
+        answer.observed_to >= derived_feed.latest_answer.timestamp(),
+        FeedError::PastTimestamp
+    );
+
+    answer.value = encode_i128(derived_feed.compute(&values)?);
+    derived_feed.latest_answer = answer;
+
+    emit!(DerivedFeedRefreshed {
+        derived_feed: derived_feed.key(),
+        answer,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(params: CreateDerivedFeedParams)]
+pub struct CreateDerivedFeed<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = DerivedFeed::SPACE,
+        seeds = [DerivedFeed::SEED_PREFIX, authority.key().as_ref(), params.name.as_bytes()],
+        bump
+    )]
+    pub derived_feed: Account<'info, DerivedFeed>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RefreshDerived<'info> {
+    #[account(mut)]
+    pub derived_feed: Account<'info, DerivedFeed>,
+}
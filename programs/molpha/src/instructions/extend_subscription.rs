@@ -12,26 +12,22 @@ pub fn extend_subscription(
     additional_duration_seconds: u64,
     additional_priority_fee_budget: u64,
 ) -> Result<()> {
-    let feed = &mut ctx.accounts.feed;
+    let feed = &mut ctx.accounts.feed.load_mut()?;
 
     require!(
         additional_duration_seconds >= 86400, // At least 1 day
         FeedError::MinimumExtensionTime
     );
 
-    let new_due_datetime = if feed.subscription_due_time > Clock::get()?.unix_timestamp {
-        feed.subscription_due_time + additional_duration_seconds as i64
-    } else {
-        Clock::get()?.unix_timestamp + additional_duration_seconds as i64
-    };
-
-    // Calculate cost for extension (like SubscriptionRegistry.extendSubscription)
-    let base_extension_cost = (feed.price_per_second_scaled * additional_duration_seconds) / ProtocolConfig::SCALAR;
-    let total_extension_cost = base_extension_cost + additional_priority_fee_budget;
-
-    // Extend subscription
-    feed.subscription_due_time = new_due_datetime;
-    feed.priority_fee_allowance += additional_priority_fee_budget;
+    // Extend subscription (like SubscriptionRegistry.extendSubscription),
+    // settling the debt accrued during the grace period
+    let settled_debt = feed.grace_debt;
+    let total_extension_cost = feed.extend_subscription(
+        Clock::get()?.unix_timestamp,
+        additional_duration_seconds,
+        additional_priority_fee_budget,
+    )?;
+    let new_due_datetime = feed.subscription_due_time;
 
     // Transfer tokens from user to program token account
     let decimals = ctx.accounts.underlying_token.decimals;
@@ -44,14 +40,12 @@ pub fn extend_subscription(
     let cpi_program = ctx.accounts.token_program.to_account_info();
     let cpi_context = CpiContext::new(cpi_program, cpi_accounts);
     transfer_checked(cpi_context, total_extension_cost, decimals)?;
-    
-    feed.balance += total_extension_cost;
 
     msg!(
-        "Subscription extended. Additional duration: {}s, Base cost: {}, Priority budget: {}, Total: {}",
+        "Subscription extended. Additional duration: {}s, Priority budget: {}, Settled debt: {}, Total: {}",
         additional_duration_seconds,
-        base_extension_cost,
         additional_priority_fee_budget,
+        settled_debt,
         total_extension_cost
     );
 
@@ -62,6 +56,7 @@ pub fn extend_subscription(
         additional_duration: additional_duration_seconds,
         additional_priority_budget: additional_priority_fee_budget,
         new_due_time: new_due_datetime,
+        settled_debt,
         extended_at: Clock::get()?.unix_timestamp,
     });
 
@@ -74,7 +69,7 @@ pub struct ExtendSubscription<'info> {
         mut,
         has_one = authority,
     )]
-    pub feed: Account<'info, Feed>,
+    pub feed: AccountLoader<'info, Feed>,
 
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -103,6 +98,7 @@ pub struct ExtendSubscription<'info> {
     pub protocol_config: Account<'info, ProtocolConfig>,
     
     /// The underlying token mint
+    #[account(address = protocol_config.underlying_token)]
     pub underlying_token: InterfaceAccount<'info, Mint>,
     
     pub system_program: Program<'info, System>,
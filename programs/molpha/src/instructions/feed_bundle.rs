@@ -0,0 +1,169 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+
+use crate::error::{BundleError, FeedError};
+use crate::events::{BundlePublished, BundleSlotsAdded, FeedBundleCreated};
+use crate::state::{
+    BundleReport, FeedBundle, NodeRegistry, MAX_BUNDLE_SLOTS, MAX_NAME_LEN, MAX_SLOT_NAME_LEN,
+};
+use crate::utils::collect_node_signers;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateFeedBundleParams {
+    pub name: String,
+    pub min_signatures_threshold: u8,
+    pub exponent: i32,
+    pub capacity: u16,
+}
+
+pub fn create_feed_bundle(
+    ctx: Context<CreateFeedBundle>,
+    params: CreateFeedBundleParams,
+) -> Result<()> {
+    require!(
+        !params.name.is_empty() && params.name.len() <= MAX_NAME_LEN,
+        BundleError::InvalidBundleConfig
+    );
+    require!(params.min_signatures_threshold > 0, BundleError::InvalidBundleConfig);
+    require!(
+        params.capacity > 0 && params.capacity as usize <= MAX_BUNDLE_SLOTS,
+        BundleError::InvalidBundleConfig
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let bundle = &mut ctx.accounts.bundle;
+    bundle.authority = ctx.accounts.authority.key();
+    bundle.name = params.name;
+    bundle.min_signatures_threshold = params.min_signatures_threshold;
+    bundle.exponent = params.exponent;
+    bundle.capacity = params.capacity;
+    bundle.created_at = now;
+    bundle.bump = ctx.bumps.bundle;
+
+    emit!(FeedBundleCreated {
+        bundle: bundle.key(),
+        authority: bundle.authority,
+        capacity: bundle.capacity,
+        exponent: bundle.exponent,
+        created_at: now,
+    });
+
+    Ok(())
+}
+
+/// Appends named slots, in as many calls as the transaction size requires.
+/// New slots cannot be read until the next report, which must cover them.
+pub fn add_bundle_slots(ctx: Context<AddBundleSlots>, names: Vec<String>) -> Result<()> {
+    let bundle = &mut ctx.accounts.bundle;
+    require!(
+        bundle.slot_names.len() + names.len() <= bundle.capacity as usize,
+        BundleError::TooManySlots
+    );
+    require!(
+        names.iter().all(|name| !name.is_empty() && name.len() <= MAX_SLOT_NAME_LEN),
+        BundleError::InvalidBundleConfig
+    );
+
+    bundle.slot_names.extend(names.iter().cloned());
+    let slots_count = bundle.slot_names.len();
+    bundle.values.resize(slots_count, 0);
+
+    emit!(BundleSlotsAdded {
+        bundle: bundle.key(),
+        names,
+        slots_count: slots_count as u16,
+    });
+
+    Ok(())
+}
+
+/// Updates every slot of a bundle from one report signed by the nodes (see
+/// `BundleReport::signing_message`), with a single quorum check.
+pub fn publish_bundle(ctx: Context<PublishBundle>, report: BundleReport) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let bundle_key = ctx.accounts.bundle.key();
+    let bundle = &mut ctx.accounts.bundle;
+
+    require!(
+        !bundle.values.is_empty() && report.values.len() == bundle.values.len(),
+        BundleError::SlotCountMismatch
+    );
+    require!(
+        report.observed_from <= report.observed_to,
+        FeedError::InvalidObservationWindow
+    );
+    require!(report.observed_to > bundle.observed_to, FeedError::PastTimestamp);
+    require!(report.observed_to <= now, FeedError::FutureTimestamp);
+
+    let unique_valid_signers = collect_node_signers(
+        &ctx.accounts.instructions,
+        &report.signing_message(&bundle_key),
+        &ctx.accounts.node_registry.nodes,
+    )?;
+    require!(
+        unique_valid_signers.len() >= bundle.min_signatures_threshold as usize,
+        FeedError::NotEnoughSignatures
+    );
+
+    bundle.values = report.values;
+    bundle.reported_slots = bundle.values.len() as u16;
+    bundle.round_id += 1;
+    bundle.observed_from = report.observed_from;
+    bundle.observed_to = report.observed_to;
+    bundle.signer_count = unique_valid_signers.len() as u8;
+
+    emit!(BundlePublished {
+        bundle: bundle_key,
+        round_id: bundle.round_id,
+        slots_count: bundle.values.len() as u16,
+        signatures_count: bundle.signer_count,
+        observed_to: bundle.observed_to,
+        published_at: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(params: CreateFeedBundleParams)]
+pub struct CreateFeedBundle<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = FeedBundle::space(params.capacity),
+        seeds = [FeedBundle::SEED_PREFIX, authority.key().as_ref(), params.name.as_bytes()],
+        bump
+    )]
+    pub bundle: Account<'info, FeedBundle>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddBundleSlots<'info> {
+    #[account(
+        mut,
+        has_one = authority
+    )]
+    pub bundle: Account<'info, FeedBundle>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PublishBundle<'info> {
+    #[account(mut)]
+    pub bundle: Account<'info, FeedBundle>,
+
+    #[account(
+        seeds = [NodeRegistry::SEED_PREFIX],
+        bump
+    )]
+    pub node_registry: Account<'info, NodeRegistry>,
+
+    /// CHECK: This is safe. We only read the instructions sysvar for validation.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
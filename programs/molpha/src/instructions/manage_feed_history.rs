@@ -0,0 +1,115 @@
+use crate::error::FeedError;
+use crate::events::{FeedHistoryCreated, FeedHistoryResized};
+use crate::state::{Feed, FeedHistory, HistoryEntry};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+use std::mem::size_of;
+
+/// Creates the history account of a feed with room for `capacity` answers.
+/// The feed authority pays its rent.
+pub fn create_feed_history(ctx: Context<CreateFeedHistory>, capacity: u32) -> Result<()> {
+    require!(
+        capacity > 0 && capacity <= FeedHistory::MAX_CAPACITY,
+        FeedError::InvalidHistoryCapacity
+    );
+
+    let mut history = ctx.accounts.history.load_init()?;
+    history.feed = ctx.accounts.feed.key();
+    history.capacity = capacity;
+    history.bump = ctx.bumps.history;
+
+    ctx.accounts.feed.load_mut()?.history = ctx.accounts.history.key();
+
+    emit!(FeedHistoryCreated {
+        feed: ctx.accounts.feed.key(),
+        history: ctx.accounts.history.key(),
+        capacity,
+        created_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Grows the history account to `new_capacity` answers, keeping the recorded
+/// ones. A single call can add up to `MAX_PERMITTED_DATA_INCREASE` bytes.
+pub fn grow_feed_history(ctx: Context<GrowFeedHistory>, new_capacity: u32) -> Result<()> {
+    let history_info = ctx.accounts.history.to_account_info();
+    let capacity = ctx.accounts.history.load()?.capacity;
+    require!(
+        new_capacity > capacity
+            && new_capacity <= FeedHistory::MAX_CAPACITY
+            && (new_capacity - capacity) as usize * size_of::<HistoryEntry>()
+                <= MAX_PERMITTED_DATA_INCREASE,
+        FeedError::InvalidHistoryCapacity
+    );
+
+    let space = FeedHistory::space(new_capacity);
+    let missing_lamports = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(history_info.lamports());
+    if missing_lamports > 0 {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: history_info.clone(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, missing_lamports)?;
+    }
+    history_info.resize(space)?;
+
+    {
+        let mut data = history_info.try_borrow_mut_data()?;
+        let (header, entries) = FeedHistory::load_mut(&mut data)?;
+        header.grow(entries, new_capacity);
+    }
+
+    emit!(FeedHistoryResized {
+        feed: ctx.accounts.feed.key(),
+        history: history_info.key(),
+        capacity: new_capacity,
+        resized_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(capacity: u32)]
+pub struct CreateFeedHistory<'info> {
+    #[account(
+        mut,
+        has_one = authority,
+        constraint = !feed.load()?.has_history() @ FeedError::InvalidHistoryAccount
+    )]
+    pub feed: AccountLoader<'info, Feed>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = FeedHistory::space(capacity),
+        seeds = [FeedHistory::SEED_PREFIX, feed.key().as_ref()],
+        bump
+    )]
+    pub history: AccountLoader<'info, FeedHistory>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GrowFeedHistory<'info> {
+    #[account(has_one = authority, has_one = history)]
+    pub feed: AccountLoader<'info, Feed>,
+
+    #[account(mut)]
+    pub history: AccountLoader<'info, FeedHistory>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
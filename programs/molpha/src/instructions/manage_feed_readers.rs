@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::error::FeedError;
+use crate::events::FeedReadersUpdated;
+use crate::state::{Feed, FeedReaders, FeedType, MAX_FEED_READERS};
+
+/// Sets the consumer programs allowed to read a personal feed. A non-empty
+/// list restricts reads to `read_answer_gated`; an empty list lifts it.
+pub fn set_feed_readers(ctx: Context<SetFeedReaders>, readers: Vec<Pubkey>) -> Result<()> {
+    require!(readers.len() <= MAX_FEED_READERS, FeedError::TooManyReaders);
+
+    let feed = &mut ctx.accounts.feed.load_mut()?;
+    require!(feed.feed_type() == FeedType::Personal, FeedError::NotSupported);
+    feed.restricted_readers = !readers.is_empty() as u8;
+
+    let feed_readers = &mut ctx.accounts.readers;
+    feed_readers.feed = ctx.accounts.feed.key();
+    feed_readers.readers = readers.clone();
+    feed_readers.bump = ctx.bumps.readers;
+
+    emit!(FeedReadersUpdated {
+        feed: feed_readers.feed,
+        authority: ctx.accounts.authority.key(),
+        readers,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetFeedReaders<'info> {
+    #[account(
+        mut,
+        has_one = authority
+    )]
+    pub feed: AccountLoader<'info, Feed>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = FeedReaders::SPACE,
+        seeds = [FeedReaders::SEED_PREFIX, feed.key().as_ref()],
+        bump
+    )]
+    pub readers: Account<'info, FeedReaders>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
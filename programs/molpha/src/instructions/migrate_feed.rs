@@ -0,0 +1,86 @@
+use crate::error::FeedError;
+use crate::events::FeedMigrated;
+use crate::state::{Feed, FeedHistory, LegacyFeed, MAX_HISTORY};
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+/// Rewrites a feed created before `AnswerV2` (`LegacyFeed`) into the
+/// zero-copy layout, resizing the account and topping up its rent from
+/// `payer`. Its history is moved into a new `FeedHistory` when `history` is
+/// passed, and dropped otherwise.
+pub fn migrate_feed(ctx: Context<MigrateFeed>) -> Result<()> {
+    let feed_info = ctx.accounts.feed.to_account_info();
+
+    let (mut feed, answers) = {
+        let data = feed_info.try_borrow_data()?;
+        require!(data.starts_with(Feed::DISCRIMINATOR), FeedError::NotLegacyFeed);
+        let mut fields = &data[Feed::DISCRIMINATOR.len()..];
+        require!(data.len() == LegacyFeed::SPACE, FeedError::NotLegacyFeed);
+        LegacyFeed::deserialize(&mut fields)?.into_feed()?
+    };
+
+    let mut history_len = 0;
+    if let Some(history) = &ctx.accounts.history {
+        let history_info = history.to_account_info();
+        let mut data = history_info.try_borrow_mut_data()?;
+        {
+            let (header, _) = FeedHistory::load_init_mut(&mut data);
+            header.feed = feed_info.key();
+            header.capacity = MAX_HISTORY as u32;
+            header.bump = ctx.bumps.history.ok_or(FeedError::InvalidHistoryAccount)?;
+        }
+        let (header, entries) = FeedHistory::load_init_mut(&mut data);
+        for entry in answers {
+            header.push(entries, entry);
+        }
+        history_len = header.len;
+        feed.history = history_info.key();
+    }
+
+    let required_lamports = Rent::get()?.minimum_balance(Feed::SPACE);
+    let missing_lamports = required_lamports.saturating_sub(feed_info.lamports());
+    if missing_lamports > 0 {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: feed_info.clone(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, missing_lamports)?;
+    }
+
+    feed_info.resize(Feed::SPACE)?;
+    feed_info.try_borrow_mut_data()?[Feed::DISCRIMINATOR.len()..]
+        .copy_from_slice(bytemuck::bytes_of(&feed));
+
+    emit!(FeedMigrated {
+        feed: feed_info.key(),
+        migrated_by: ctx.accounts.payer.key(),
+        history_len: history_len as u8,
+        migrated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateFeed<'info> {
+    /// CHECK: Feed in a previous layout, verified and deserialized in the handler
+    #[account(mut, owner = crate::ID)]
+    pub feed: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = FeedHistory::space(MAX_HISTORY as u32),
+        seeds = [FeedHistory::SEED_PREFIX, feed.key().as_ref()],
+        bump
+    )]
+    pub history: Option<AccountLoader<'info, FeedHistory>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,117 @@
+use crate::error::FeedError;
+use crate::events::FeedAddressMigrated;
+use crate::state::{Feed, FeedHistory, FeedReaders};
+use anchor_lang::prelude::*;
+
+/// Moves a feed from its previous address, which was derived from mutable
+/// config fields, to the address derived from its creator and `name`. Old
+/// feeds that differed only by config map to the same address under their
+/// current name, so `name` may differ from it; the feed is renamed to it.
+/// The new account is paid by the authority and the old one is closed to it.
+/// The feed's history is relinked and its `FeedReaders` list is moved to the
+/// new address. Feeds already at their creator and name address cannot move,
+/// and feeds with running subscriptions cannot move until they lapse, as
+/// rounds, `PriceUpdate` and `Subscription` accounts of the old address are
+/// left behind.
+pub fn migrate_feed_address(ctx: Context<MigrateFeedAddress>, name: String) -> Result<()> {
+    let old_feed = ctx.accounts.old_feed.load()?;
+    let (current_address, _) = Pubkey::find_program_address(
+        &[Feed::SEED_PREFIX, old_feed.creator.as_ref(), old_feed.name().as_bytes()],
+        ctx.program_id,
+    );
+    require_keys_neq!(
+        ctx.accounts.old_feed.key(),
+        current_address,
+        FeedError::FeedAlreadyMigrated
+    );
+    require!(
+        !old_feed.has_active_subscribers(Clock::get()?.unix_timestamp),
+        FeedError::ActiveSubscriptions
+    );
+
+    if old_feed.has_history() {
+        let history = ctx
+            .accounts
+            .history
+            .as_ref()
+            .ok_or(error!(FeedError::InvalidHistoryAccount))?;
+        require_keys_eq!(history.key(), old_feed.history, FeedError::InvalidHistoryAccount);
+        history.load_mut()?.feed = ctx.accounts.new_feed.key();
+    }
+
+    let new_feed_key = ctx.accounts.new_feed.key();
+    match (&ctx.accounts.old_readers, &mut ctx.accounts.new_readers) {
+        (Some(old_readers), Some(new_readers)) => {
+            new_readers.feed = new_feed_key;
+            new_readers.readers = old_readers.readers.clone();
+            new_readers.bump = ctx.bumps.new_readers.ok_or(FeedError::InvalidReadersAccount)?;
+        }
+        (_, None) if !old_feed.has_restricted_readers() => {}
+        _ => return err!(FeedError::InvalidReadersAccount),
+    }
+
+    let new_feed = &mut ctx.accounts.new_feed.load_init()?;
+    **new_feed = *old_feed;
+    new_feed.set_name(&name)?;
+    new_feed.bump = ctx.bumps.new_feed;
+
+    emit!(FeedAddressMigrated {
+        old_feed: ctx.accounts.old_feed.key(),
+        new_feed: ctx.accounts.new_feed.key(),
+        authority: ctx.accounts.authority.key(),
+        migrated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct MigrateFeedAddress<'info> {
+    #[account(
+        mut,
+        has_one = authority,
+        close = authority
+    )]
+    pub old_feed: AccountLoader<'info, Feed>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Feed::SPACE,
+        seeds = [
+            Feed::SEED_PREFIX,
+            old_feed.load()?.creator.as_ref(),
+            name.as_bytes(),
+        ],
+        bump
+    )]
+    pub new_feed: AccountLoader<'info, Feed>,
+
+    /// The feed's history account, required when it has one
+    #[account(mut)]
+    pub history: Option<AccountLoader<'info, FeedHistory>>,
+
+    /// The feed's reader list, required when reads are restricted
+    #[account(
+        mut,
+        close = authority,
+        seeds = [FeedReaders::SEED_PREFIX, old_feed.key().as_ref()],
+        bump = old_readers.bump
+    )]
+    pub old_readers: Option<Account<'info, FeedReaders>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = FeedReaders::SPACE,
+        seeds = [FeedReaders::SEED_PREFIX, new_feed.key().as_ref()],
+        bump
+    )]
+    pub new_readers: Option<Account<'info, FeedReaders>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,68 @@
+use crate::error::ProtocolError;
+use crate::events::ProtocolConfigMigrated;
+use crate::state::{ProtocolConfig, PROTOCOL_CONFIG_V1_SPACE};
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+/// Rewrites a protocol config created with fewer fields into the current
+/// layout, resizing the account and topping up its rent from `payer`. New
+/// fields start at zero: no transmitter schedule, crank tip or grace period.
+pub fn migrate_protocol_config(ctx: Context<MigrateProtocolConfig>) -> Result<()> {
+    let config_info = ctx.accounts.protocol_config.to_account_info();
+    let previous_space = config_info.data_len();
+
+    let config = {
+        let data = config_info.try_borrow_data()?;
+        require!(
+            data.starts_with(ProtocolConfig::DISCRIMINATOR),
+            ProtocolError::InvalidProtocolConfig
+        );
+        require!(
+            (PROTOCOL_CONFIG_V1_SPACE..ProtocolConfig::SPACE).contains(&data.len()),
+            ProtocolError::ProtocolConfigUpToDate
+        );
+        ProtocolConfig::from_previous_layout(&data[ProtocolConfig::DISCRIMINATOR.len()..])?
+    };
+
+    let required_lamports = Rent::get()?.minimum_balance(ProtocolConfig::SPACE);
+    let missing_lamports = required_lamports.saturating_sub(config_info.lamports());
+    if missing_lamports > 0 {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: config_info.clone(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, missing_lamports)?;
+    }
+
+    config_info.resize(ProtocolConfig::SPACE)?;
+    config.try_serialize(&mut &mut config_info.try_borrow_mut_data()?[..])?;
+
+    emit!(ProtocolConfigMigrated {
+        protocol_config: config_info.key(),
+        migrated_by: ctx.accounts.payer.key(),
+        previous_space: previous_space as u16,
+        migrated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateProtocolConfig<'info> {
+    /// CHECK: Protocol config in a previous layout, verified and deserialized in the handler
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump
+    )]
+    pub protocol_config: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
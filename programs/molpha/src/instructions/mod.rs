@@ -1,19 +1,55 @@
 // Node management instructions from molpha-solana
+pub mod auto_renew;
+pub mod close_feed;
 pub mod create_data_source;
 pub mod create_feed;
+pub mod derived_feed;
+pub mod feed_bundle;
 pub mod extend_subscription;
 pub mod initialize;
+pub mod manage_feed_history;
+pub mod manage_feed_readers;
 pub mod manage_node;
+pub mod migrate_feed;
+pub mod migrate_feed_address;
+pub mod migrate_protocol_config;
 pub mod publish_answer;
+pub mod publish_batch;
+pub mod read_answer;
+pub mod read_history;
+pub mod submit_observation;
+pub mod set_feed_status;
+pub mod subscribe_to_feed;
 pub mod top_up;
+pub mod transfer_feed_authority;
 pub mod update_feed_config;
+pub mod update_protocol_config;
+pub mod verify_report;
 
 // Re-export all instruction structs and functions
+pub use auto_renew::*;
+pub use close_feed::*;
 pub use create_data_source::*;
 pub use create_feed::*;
+pub use derived_feed::*;
+pub use feed_bundle::*;
 pub use extend_subscription::*;
 pub use initialize::*;
+pub use manage_feed_history::*;
+pub use manage_feed_readers::*;
 pub use manage_node::*;
+pub use migrate_feed::*;
+pub use migrate_feed_address::*;
+pub use migrate_protocol_config::*;
 pub use publish_answer::*;
+pub use publish_batch::*;
+pub use read_answer::*;
+pub use read_history::*;
+pub use submit_observation::*;
+pub use set_feed_status::*;
+pub use subscribe_to_feed::*;
 pub use top_up::*;
+pub use transfer_feed_authority::*;
 pub use update_feed_config::*;
+pub use update_protocol_config::*;
+pub use verify_report::*;
@@ -1,74 +1,184 @@
 use crate::error::FeedError;
-use crate::events::AnswerPublished;
-use crate::state::{
-    Answer, Feed, NodeRegistry, MAX_HISTORY
+use crate::events::{
+    AnswerPublished, AnswerRejectedOutlier, RoundSuperseded, SubscriptionInGrace, SubscriptionLow,
+    TransmitterReimbursed,
 };
-use crate::utils::{parse_ed25519_instruction, pricing::*};
+use crate::state::{record_history, AnswerReport, AnswerV2, Feed, NodeRegistry, ProtocolConfig};
+use crate::utils::{collect_node_signers, metering::TransactionFees};
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::{ed25519_program, sysvar};
+use anchor_lang::solana_program::sysvar;
+use anchor_spl::token_interface::{
+    Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked,
+};
+
+pub fn publish_answer(ctx: Context<PublishAnswer>, report: AnswerReport) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    // Meter the fees paid by this transaction
+    let config = &ctx.accounts.protocol_config;
+    let metered_fee = TransactionFees::from_instructions(&ctx.accounts.instructions)?
+        .covered_fee(config.max_priority_fee_coverage);
+
+    // Validate signatures from registered nodes
+    let unique_valid_signers = collect_node_signers(
+        &ctx.accounts.instructions,
+        &report.signing_message(&ctx.accounts.feed.key()),
+        &ctx.accounts.node_registry.nodes,
+    )?;
+
+    // Only the scheduled transmitter is reimbursed
+    let scheduled = check_transmitter(
+        config,
+        &ctx.accounts.node_registry.nodes,
+        &ctx.accounts.feed,
+        &ctx.accounts.transmitter.key(),
+        now,
+    )?;
+
+    let charged_fee = apply_report(
+        &ctx.accounts.feed,
+        &report,
+        unique_valid_signers.len(),
+        ctx.accounts.node_registry.nodes.len(),
+        scheduled.then_some(metered_fee),
+        config,
+        now,
+    )?;
+
+    let Some(charged_fee) = charged_fee else {
+        msg!("Answer rejected as an outlier");
+        return Ok(());
+    };
+    record_history(&*ctx.accounts.feed.load()?, ctx.accounts.history.as_deref())?;
+
+    reimburse_transmitter(
+        &ctx.accounts.protocol_config,
+        &ctx.accounts.program_token_account,
+        &ctx.accounts.transmitter_token_account,
+        &ctx.accounts.underlying_token,
+        &ctx.accounts.token_program,
+        charged_fee,
+    )?;
+
+    msg!(
+        "Successfully published answer with {} valid signatures. Priority fee: {}",
+        unique_valid_signers.len(),
+        charged_fee
+    );
 
-pub fn publish_answer(ctx: Context<PublishAnswer>, answer: Answer) -> Result<()> {
-    let feed = &mut ctx.accounts.feed;
-    let clock = Clock::get()?;
+    Ok(())
+}
+
+/// Enforces the transmitter schedule for the next round of `feed`: during the
+/// exclusive part of a turn only the designated transmitter may submit.
+/// Returns whether the transmitter was scheduled and should be reimbursed.
+pub(crate) fn check_transmitter(
+    config: &ProtocolConfig,
+    nodes: &[Pubkey],
+    feed: &AccountLoader<Feed>,
+    transmitter: &Pubkey,
+    now: i64,
+) -> Result<bool> {
+    let round_id = feed.load()?.next_round_id();
+    match config.designated_transmitter(nodes, &feed.key(), round_id, now) {
+        None => Ok(true),
+        Some(designated) if designated == *transmitter => Ok(true),
+        Some(_) => {
+            require!(
+                config.is_transmitter_fallback_open(now),
+                FeedError::NotDesignatedTransmitter
+            );
+            Ok(false)
+        }
+    }
+}
+
+/// Pays the transmitter from the program token account, signed by the
+/// `ProtocolConfig` PDA that owns it.
+pub(crate) fn reimburse_transmitter<'info>(
+    protocol_config: &Account<'info, ProtocolConfig>,
+    program_token_account: &InterfaceAccount<'info, TokenAccount>,
+    transmitter_token_account: &InterfaceAccount<'info, TokenAccount>,
+    underlying_token: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let signer_seeds: &[&[&[u8]]] = &[&[ProtocolConfig::SEED_PREFIX, &[protocol_config.bump]]];
+    let cpi_accounts = TransferChecked {
+        from: program_token_account.to_account_info(),
+        to: transmitter_token_account.to_account_info(),
+        authority: protocol_config.to_account_info(),
+        mint: underlying_token.to_account_info(),
+    };
+    let cpi_context = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    transfer_checked(cpi_context, amount, underlying_token.decimals)?;
 
+    emit!(TransmitterReimbursed {
+        transmitter: transmitter_token_account.owner,
+        amount,
+        reimbursed_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Validates a signed report against the feed, charges the metered fee up to
+/// the remaining priority fee budget and records the answer. Shared by single
+/// and batch publishing.
+/// Without a `metered_fee` (unscheduled transmitter) nothing is charged.
+/// Past the due time, reports are accepted for the config's grace period and
+/// accrue debt.
+/// Returns the fee charged to the feed, or `None` when the outlier guard
+/// drops the answer.
+pub(crate) fn apply_report(
+    feed: &AccountLoader<Feed>,
+    report: &AnswerReport,
+    signers_count: usize,
+    node_count: usize,
+    metered_fee: Option<u64>,
+    config: &ProtocolConfig,
+    now: i64,
+) -> Result<Option<u64>> {
+    let feed_key = feed.key();
+    let feed = &mut feed.load_mut()?;
+
+    require!(feed.status().accepts_answers(), FeedError::FeedRetired);
     // Check if subscription is active
     require!(
-        feed.is_subscription_active(clock.unix_timestamp),
+        feed.accepts_publishes(now, config.grace_period),
         FeedError::SubscriptionExpired
     );
 
+    require!(report.has_valid_window(), FeedError::InvalidObservationWindow);
     require!(
-        answer.timestamp > feed.latest_answer.timestamp,
-        FeedError::PastTimestamp
+        feed.value_type()
+            .is_valid(&report.value, report.exponent, report.confidence),
+        FeedError::InvalidValue
     );
     require!(
-        answer.timestamp <= clock.unix_timestamp,
-        FeedError::FutureTimestamp
-    );
-
-    // Calculate actual priority fee paid
-    let estimated_compute_units = estimate_compute_units(
-        ctx.accounts.node_registry.nodes.len() as u32,
-        feed.answer_history.len() as u32,
+        report.observed_to > feed.latest_answer.timestamp(),
+        FeedError::PastTimestamp
     );
-    
-    let priority_fee = calculate_priority_fee_from_instructions(
-        &ctx.accounts.instructions,
-        estimated_compute_units,
-    )?;
-
-    // Check priority fee budgetAS
     require!(
-        feed.has_priority_fee_budget(priority_fee),
-        FeedError::InsufficientPriorityFeeBudget
+        report.observed_to <= now,
+        FeedError::FutureTimestamp
     );
 
-    // Validate signatures from registered nodes
-    let instructions_sysvar = &ctx.accounts.instructions;
-    let current_instruction_index =
-        sysvar::instructions::load_current_index_checked(instructions_sysvar)?;
-
-    let mut unique_valid_signers = Vec::new();
-    let message = answer.value.to_vec();
-
-    for i in (0..current_instruction_index).rev() {
-        let instruction =
-            sysvar::instructions::load_instruction_at_checked(i as usize, instructions_sysvar)?;
-
-        if instruction.program_id == ed25519_program::ID {
-            if let Ok((signer_pubkey, signed_message)) = parse_ed25519_instruction(&instruction) {
-                if signed_message == message
-                    && ctx.accounts.node_registry.nodes.contains(&signer_pubkey)
-                    && !unique_valid_signers.contains(&signer_pubkey)
-                {
-                    unique_valid_signers.push(signer_pubkey);
-                }
-            }
-        }
-    }
+    // Charge the metered fee, bounded by the remaining priority fee budget
+    let priority_fee = metered_fee
+        .unwrap_or_default()
+        .min(feed.remaining_priority_fee_budget());
 
     require!(
-        unique_valid_signers.len() >= feed.min_signatures_threshold as usize,
+        signers_count >= feed.min_signatures_threshold as usize,
         FeedError::NotEnoughSignatures
     );
 
@@ -78,55 +188,97 @@ pub fn publish_answer(ctx: Context<PublishAnswer>, answer: Answer) -> Result<()>
         FeedError::InsufficientBalance
     );
     
+    let answer = AnswerV2::from_report(report, feed.next_round_id(), signers_count as u8);
+    feed.check_update_policy(&answer)?;
+
+    // Outlier guard: large jumps need the elevated quorum. The answer is dropped
+    // without failing the transaction so that the rejection stays on record.
+    let outlier_quorum = feed.outlier_quorum(node_count);
+    if feed.is_outlier(&answer) && signers_count < outlier_quorum {
+        emit!(AnswerRejectedOutlier {
+            feed: feed_key,
+            answer,
+            deviation_bps: answer.deviation_bps(&feed.latest_answer(), feed.value_type()),
+            signatures_count: signers_count as u8,
+            required_signatures: outlier_quorum as u8,
+            rejected_at: now,
+        });
+        return Ok(None);
+    }
+
     feed.balance -= priority_fee;
     feed.consumed_priority_fees += priority_fee;
+    // The smoothed average is kept for accounting only
+    if let Some(fee) = metered_fee {
+        let average = feed.smoothed_priority_fee(fee, config.priority_fee_smoothing_window);
+        feed.record_priority_fee(average);
+    }
 
-    feed.latest_answer = answer;
-
-    // Use a ring buffer for history
-    if feed.answer_history.len() < MAX_HISTORY {
-        feed.answer_history.push(answer);
-        feed.history_idx = feed.answer_history.len() as u64;
-    } else {
-        let history_idx = feed.history_idx as usize;
-        feed.answer_history[history_idx] = answer;
-        feed.history_idx = (history_idx as u64 + 1) % MAX_HISTORY as u64;
+    if feed.reporting_round_id > feed.latest_round_id {
+        emit!(RoundSuperseded {
+            feed: feed_key,
+            round_id: feed.reporting_round_id,
+            superseded_by: answer.round_id,
+            superseded_at: now,
+        });
     }
 
-    msg!(
-        "Successfully published answer with {} valid signatures. Priority fee: {}",
-        unique_valid_signers.len(),
-        priority_fee
-    );
+    feed.push_answer(answer);
 
     // Emit event
     emit!(AnswerPublished {
-        feed: ctx.accounts.feed.key(),
+        feed: feed_key,
         answer,
-        signatures_count: unique_valid_signers.len() as u8,
-        published_at: clock.unix_timestamp,
+        signatures_count: signers_count as u8,
+        published_at: now,
     });
 
-    Ok(())
+    emit_subscription_warnings(feed_key, feed, config, now);
+
+    Ok(Some(priority_fee))
+}
+
+/// Warns the feed authority after a publish: `SubscriptionInGrace` past the
+/// due time, accruing the debt, or `SubscriptionLow` when the remaining time
+/// or priority fee budget is below the config's thresholds.
+pub(crate) fn emit_subscription_warnings(feed_key: Pubkey, feed: &mut Feed, config: &ProtocolConfig, now: i64) {
+    if !feed.is_subscription_active(now) {
+        feed.accrue_grace_debt(now);
+        emit!(SubscriptionInGrace {
+            feed: feed_key,
+            due_time: feed.covered_until(),
+            grace_ends_at: feed.covered_until().saturating_add(config.grace_period as i64),
+            debt: feed.grace_debt,
+            emitted_at: now,
+        });
+        return;
+    }
+
+    let remaining_time = feed.remaining_subscription_time(now);
+    let remaining_priority_budget = feed.remaining_priority_fee_budget();
+    let low_time = remaining_time < config.low_subscription_threshold as i64;
+    let low_budget = remaining_priority_budget < config.low_priority_budget_threshold;
+    if low_time || low_budget {
+        emit!(SubscriptionLow {
+            feed: feed_key,
+            remaining_time,
+            remaining_priority_budget,
+            emitted_at: now,
+        });
+    }
 }
 
 #[derive(Accounts)]
 pub struct PublishAnswer<'info> {
     #[account(
         mut,
-        constraint = feed.is_subscription_active(Clock::get()?.unix_timestamp) @ FeedError::SubscriptionExpired,
-        seeds = [
-            Feed::SEED_PREFIX, 
-            feed.authority.as_ref(), 
-            feed.name.as_bytes().as_ref(), 
-            feed.feed_type.to_seed().as_ref(), 
-            feed.min_signatures_threshold.to_le_bytes().as_ref(), 
-            feed.frequency.to_le_bytes().as_ref(), 
-            feed.job_id.as_ref()
-        ],
-        bump = feed.bump
+        constraint = feed.load()?.accepts_publishes(Clock::get()?.unix_timestamp, protocol_config.grace_period) @ FeedError::SubscriptionExpired,
     )]
-    pub feed: Account<'info, Feed>,
+    pub feed: AccountLoader<'info, Feed>,
+
+    /// CHECK: The feed's history account, required when it has one. Checked in `record_history`.
+    #[account(mut)]
+    pub history: Option<UncheckedAccount<'info>>,
 
     #[account(
         mut,
@@ -135,7 +287,38 @@ pub struct PublishAnswer<'info> {
     )]
     pub node_registry: Account<'info, NodeRegistry>,
 
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
     /// CHECK: This is safe. We only read the instructions sysvar for validation.
     #[account(address = sysvar::instructions::ID)]
     pub instructions: UncheckedAccount<'info>,
+
+    /// Submits the transaction, reimbursed when scheduled (see `check_transmitter`)
+    pub transmitter: Signer<'info>,
+
+    /// Transmitter's token account receiving the reimbursement
+    #[account(
+        mut,
+        token::mint = underlying_token,
+        token::authority = transmitter,
+    )]
+    pub transmitter_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Program's associated token account paying the reimbursement
+    #[account(
+        mut,
+        associated_token::mint = underlying_token,
+        associated_token::authority = protocol_config,
+    )]
+    pub program_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The underlying token mint
+    #[account(address = protocol_config.underlying_token)]
+    pub underlying_token: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
 }
@@ -0,0 +1,152 @@
+use crate::error::FeedError;
+use crate::events::BatchPublished;
+use crate::instructions::publish_answer::{apply_report, check_transmitter, reimburse_transmitter};
+use crate::state::{record_history, AnswerReport, Feed, NodeRegistry, ProtocolConfig};
+use crate::utils::merkle::{leaf_hash, verify_proof};
+use crate::utils::{collect_node_signers, metering::TransactionFees};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+/// An answer for one feed of a batch, with its proof against the batch root.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BatchLeaf {
+    pub report: AnswerReport,
+    pub proof: Vec<[u8; 32]>,
+}
+
+/// Publishes answers for many feeds from a single signed Merkle root.
+/// Nodes sign the 32-byte root; the feeds are passed as remaining accounts,
+/// in the same order as `leaves`, each followed by its history account when
+/// it has one.
+pub fn publish_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PublishBatch<'info>>,
+    root: [u8; 32],
+    leaves: Vec<BatchLeaf>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(!leaves.is_empty(), FeedError::EmptyBatch);
+
+    // Validate signatures over the root once for the whole batch
+    let unique_valid_signers = collect_node_signers(
+        &ctx.accounts.instructions,
+        &root,
+        &ctx.accounts.node_registry.nodes,
+    )?;
+
+    // The transaction fees are shared evenly between the feeds of the batch
+    let config = &ctx.accounts.protocol_config;
+    let metered_fee = TransactionFees::from_instructions(&ctx.accounts.instructions)?
+        .covered_fee(config.max_priority_fee_coverage)
+        / leaves.len() as u64;
+
+    let node_count = ctx.accounts.node_registry.nodes.len();
+    let mut reimbursement = 0u64;
+    let mut accounts = ctx.remaining_accounts.iter();
+    for leaf in leaves.iter() {
+        let feed_info = accounts.next().ok_or(FeedError::BatchAccountsMismatch)?;
+        require!(feed_info.is_writable, FeedError::BatchAccountsMismatch);
+        require!(
+            verify_proof(leaf_hash(feed_info.key, &leaf.report), &leaf.proof, &root),
+            FeedError::InvalidMerkleProof
+        );
+
+        let feed = AccountLoader::<Feed>::try_from(feed_info)?;
+        let history = if feed.load()?.has_history() {
+            Some(accounts.next().ok_or(FeedError::BatchAccountsMismatch)?)
+        } else {
+            None
+        };
+        let scheduled = check_transmitter(
+            config,
+            &ctx.accounts.node_registry.nodes,
+            &feed,
+            &ctx.accounts.transmitter.key(),
+            now,
+        )?;
+
+        let charged_fee = apply_report(
+            &feed,
+            &leaf.report,
+            unique_valid_signers.len(),
+            node_count,
+            scheduled.then_some(metered_fee),
+            config,
+            now,
+        )?;
+        if charged_fee.is_some() {
+            record_history(&*feed.load()?, history)?;
+        }
+        reimbursement += charged_fee.unwrap_or_default();
+    }
+    require!(accounts.next().is_none(), FeedError::BatchAccountsMismatch);
+
+    reimburse_transmitter(
+        &ctx.accounts.protocol_config,
+        &ctx.accounts.program_token_account,
+        &ctx.accounts.transmitter_token_account,
+        &ctx.accounts.underlying_token,
+        &ctx.accounts.token_program,
+        reimbursement,
+    )?;
+
+    msg!(
+        "Published batch of {} answers with {} valid signatures",
+        leaves.len(),
+        unique_valid_signers.len()
+    );
+
+    emit!(BatchPublished {
+        root,
+        feeds_count: leaves.len() as u8,
+        signatures_count: unique_valid_signers.len() as u8,
+        published_at: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct PublishBatch<'info> {
+    #[account(
+        seeds = [NodeRegistry::SEED_PREFIX],
+        bump
+    )]
+    pub node_registry: Account<'info, NodeRegistry>,
+
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: This is safe. We only read the instructions sysvar for validation.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// Submits the transaction, reimbursed when scheduled (see `check_transmitter`)
+    pub transmitter: Signer<'info>,
+
+    /// Transmitter's token account receiving the reimbursement
+    #[account(
+        mut,
+        token::mint = underlying_token,
+        token::authority = transmitter,
+    )]
+    pub transmitter_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Program's associated token account paying the reimbursement
+    #[account(
+        mut,
+        associated_token::mint = underlying_token,
+        associated_token::authority = protocol_config,
+    )]
+    pub program_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The underlying token mint
+    #[account(address = protocol_config.underlying_token)]
+    pub underlying_token: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
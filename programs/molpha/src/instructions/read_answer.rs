@@ -0,0 +1,122 @@
+use crate::error::FeedError;
+use crate::state::{AnswerV2, DerivedFeed, Feed, FeedBundle, FeedReaders, Subscription};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+
+/// Returns the latest answer as return data, guarded by `max_age_seconds`
+/// and `min_signers`. See `Feed::read_latest_answer` for the failure cases.
+/// Feeds with restricted readers fail with `FeedError::ReaderNotAllowed`
+/// and are read through `read_answer_gated`.
+pub fn read_answer(
+    ctx: Context<ReadAnswer>,
+    max_age_seconds: u64,
+    min_signers: u8,
+) -> Result<AnswerV2> {
+    let now = Clock::get()?.unix_timestamp;
+    let feed = ctx.accounts.feed.load()?;
+    require!(!feed.has_restricted_readers(), FeedError::ReaderNotAllowed);
+    feed.read_latest_answer(now, max_age_seconds, min_signers)
+}
+
+/// Same as `read_answer`, for a `DerivedFeed`.
+pub fn read_derived_answer(
+    ctx: Context<ReadDerivedAnswer>,
+    max_age_seconds: u64,
+    min_signers: u8,
+) -> Result<AnswerV2> {
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts
+        .derived_feed
+        .read_latest_answer(now, max_age_seconds, min_signers)
+}
+
+/// Reads slot `index` of a `FeedBundle` as an answer, with the same guards
+/// as `read_answer`. Fails with `BundleError::SlotNotFound` for an unknown
+/// slot.
+pub fn read_bundle_slot(
+    ctx: Context<ReadBundleSlot>,
+    index: u16,
+    max_age_seconds: u64,
+    min_signers: u8,
+) -> Result<AnswerV2> {
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts
+        .bundle
+        .slot_answer(index, now, max_age_seconds, min_signers)
+}
+
+/// Same as `read_answer`, for feeds with restricted readers. Fails with
+/// `FeedError::ReaderNotAllowed` unless the calling program, found through
+/// the instructions sysvar, is in the feed's `FeedReaders` and calls Molpha
+/// directly from the top-level instruction.
+pub fn read_answer_gated(
+    ctx: Context<ReadAnswerGated>,
+    max_age_seconds: u64,
+    min_signers: u8,
+) -> Result<AnswerV2> {
+    ctx.accounts.readers.check_caller(&ctx.accounts.instructions)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts
+        .feed
+        .load()?
+        .read_latest_answer(now, max_age_seconds, min_signers)
+}
+
+/// Same as `read_answer`, for a subscriber of a public feed. Also fails with
+/// `FeedError::SubscriptionExpired` when the subscriber's own subscription
+/// has run out.
+pub fn read_subscribed_answer(
+    ctx: Context<ReadSubscribedAnswer>,
+    max_age_seconds: u64,
+    min_signers: u8,
+) -> Result<AnswerV2> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        ctx.accounts.subscription.is_active(now),
+        FeedError::SubscriptionExpired
+    );
+    ctx.accounts
+        .feed
+        .load()?
+        .read_latest_answer(now, max_age_seconds, min_signers)
+}
+
+#[derive(Accounts)]
+pub struct ReadAnswer<'info> {
+    pub feed: AccountLoader<'info, Feed>,
+}
+
+#[derive(Accounts)]
+pub struct ReadDerivedAnswer<'info> {
+    pub derived_feed: Account<'info, DerivedFeed>,
+}
+
+#[derive(Accounts)]
+pub struct ReadBundleSlot<'info> {
+    pub bundle: Account<'info, FeedBundle>,
+}
+
+#[derive(Accounts)]
+pub struct ReadAnswerGated<'info> {
+    pub feed: AccountLoader<'info, Feed>,
+    #[account(
+        seeds = [FeedReaders::SEED_PREFIX, feed.key().as_ref()],
+        bump = readers.bump,
+    )]
+    pub readers: Account<'info, FeedReaders>,
+    /// CHECK: This is safe. We only read the instructions sysvar to find the caller.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReadSubscribedAnswer<'info> {
+    pub feed: AccountLoader<'info, Feed>,
+    #[account(
+        seeds = [Subscription::SEED_PREFIX, feed.key().as_ref(), subscriber.key().as_ref()],
+        bump = subscription.bump,
+    )]
+    pub subscription: Account<'info, Subscription>,
+    pub subscriber: Signer<'info>,
+}
@@ -0,0 +1,102 @@
+use crate::error::FeedError;
+use crate::state::{AverageAnswer, Feed, FeedHistory, FeedReaders, HistoricalAnswer};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+
+// History reads fail while the feed is paused or retired. Feeds with
+// restricted readers need `readers` and `instructions`, and are only readable
+// by the programs in the list, as with `read_answer_gated`.
+
+/// Returns the answer in effect at `timestamp` as return data, with its age at
+/// that time. See `FeedHistory::answer_at`.
+pub fn get_answer_at(ctx: Context<ReadHistory>, timestamp: i64) -> Result<HistoricalAnswer> {
+    ctx.accounts.check_access()?;
+    require!(
+        timestamp <= Clock::get()?.unix_timestamp,
+        FeedError::FutureTimestamp
+    );
+
+    let data = ctx.accounts.history.as_ref().try_borrow_data()?;
+    let (history, entries) = FeedHistory::load(&data)?;
+    history
+        .answer_at(entries, timestamp)
+        .ok_or(error!(FeedError::AnswerNotFound))
+}
+
+/// Returns the answer of round `round_id` as return data, with its current age.
+/// See `FeedHistory::round`.
+pub fn get_round(ctx: Context<ReadHistory>, round_id: u64) -> Result<HistoricalAnswer> {
+    ctx.accounts.check_access()?;
+    let now = Clock::get()?.unix_timestamp;
+
+    let data = ctx.accounts.history.as_ref().try_borrow_data()?;
+    let (history, entries) = FeedHistory::load(&data)?;
+    history
+        .round(entries, round_id, now)
+        .ok_or(error!(FeedError::AnswerNotFound))
+}
+
+/// Returns the TWAP over the last `window_seconds` and the EMA of a numeric
+/// feed as return data. See `FeedHistory::twap`. Fails if the exponent
+/// changed within the window.
+pub fn get_twap(ctx: Context<ReadHistory>, window_seconds: u64) -> Result<AverageAnswer> {
+    ctx.accounts.check_access()?;
+    let now = Clock::get()?.unix_timestamp;
+    let feed = ctx.accounts.feed.load()?;
+    let value_type = feed.value_type();
+    require!(value_type.is_numeric(), FeedError::NotSupported);
+
+    let window_start = now.saturating_sub(i64::try_from(window_seconds).unwrap_or(i64::MAX));
+    let data = ctx.accounts.history.as_ref().try_borrow_data()?;
+    let (history, entries) = FeedHistory::load(&data)?;
+    let twap = history
+        .twap(entries, value_type, window_start, now)
+        .ok_or(error!(FeedError::WindowNotCovered))?;
+    let exponent = history
+        .window_exponent(entries, window_start, now)
+        .ok_or(error!(FeedError::ExponentChanged))?;
+
+    Ok(AverageAnswer {
+        twap,
+        ema: feed.ema(),
+        exponent,
+        window_start,
+        window_end: now,
+    })
+}
+
+#[derive(Accounts)]
+pub struct ReadHistory<'info> {
+    #[account(has_one = history @ FeedError::InvalidHistoryAccount)]
+    pub feed: AccountLoader<'info, Feed>,
+
+    pub history: AccountLoader<'info, FeedHistory>,
+
+    /// The feed's reader list, required when reads are restricted
+    #[account(
+        seeds = [FeedReaders::SEED_PREFIX, feed.key().as_ref()],
+        bump = readers.bump,
+    )]
+    pub readers: Option<Account<'info, FeedReaders>>,
+
+    /// CHECK: This is safe. We only read the instructions sysvar to find the caller.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+}
+
+impl ReadHistory<'_> {
+    /// Fails while the feed is paused or retired, as `read_answer` does, and
+    /// applies the reader allow-list of `read_answer_gated` when the feed
+    /// restricts its readers.
+    fn check_access(&self) -> Result<()> {
+        let feed = self.feed.load()?;
+        feed.check_readable()?;
+        if !feed.has_restricted_readers() {
+            return Ok(());
+        }
+        match (&self.readers, &self.instructions) {
+            (Some(readers), Some(instructions)) => readers.check_caller(instructions),
+            _ => err!(FeedError::ReaderNotAllowed),
+        }
+    }
+}
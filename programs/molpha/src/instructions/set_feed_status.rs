@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+
+use crate::error::FeedError;
+use crate::events::FeedStatusUpdated;
+use crate::state::{Feed, FeedStatus, FeedSuccessor, ProtocolConfig};
+
+/// Moves a feed to `status`, by its authority or the protocol admin. See
+/// `FeedStatus::can_transition_to` for the allowed transitions. A feed paused
+/// by the admin can only be moved by the admin. Deprecating a feed records
+/// its successor in `successor`, paid by the signer.
+pub fn set_feed_status(ctx: Context<SetFeedStatus>, status: FeedStatus) -> Result<()> {
+    let feed_key = ctx.accounts.feed.key();
+    let signer = ctx.accounts.authority.key();
+    let feed = &mut ctx.accounts.feed.load_mut()?;
+
+    let is_admin = signer == ctx.accounts.protocol_config.authority;
+    require!(
+        is_admin || signer == feed.authority,
+        FeedError::Unauthorized
+    );
+    require!(
+        is_admin || !feed.is_paused_by_admin(),
+        FeedError::Unauthorized
+    );
+    let previous_status = match (feed.status(), &ctx.accounts.successor) {
+        (FeedStatus::Deprecated { .. }, Some(record)) => FeedStatus::Deprecated {
+            successor: record.successor,
+        },
+        (previous_status, _) => previous_status,
+    };
+    require!(
+        previous_status.can_transition_to(&status, is_admin),
+        FeedError::InvalidStatusTransition
+    );
+    if let Some(successor) = status.successor() {
+        require!(
+            successor != Pubkey::default() && successor != feed_key,
+            FeedError::InvalidStatusTransition
+        );
+        let record = ctx
+            .accounts
+            .successor
+            .as_mut()
+            .ok_or(error!(FeedError::InvalidStatusTransition))?;
+        record.feed = feed_key;
+        record.successor = successor;
+        record.bump = ctx.bumps.successor.ok_or(FeedError::InvalidStatusTransition)?;
+    }
+
+    feed.set_status(&status, is_admin);
+
+    emit!(FeedStatusUpdated {
+        feed: feed_key,
+        updated_by: signer,
+        previous_status,
+        status,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetFeedStatus<'info> {
+    #[account(mut)]
+    pub feed: AccountLoader<'info, Feed>,
+
+    /// Feed authority or protocol admin
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// The feed's successor record, required to deprecate the feed
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = FeedSuccessor::SPACE,
+        seeds = [FeedSuccessor::SEED_PREFIX, feed.key().as_ref()],
+        bump
+    )]
+    pub successor: Option<Account<'info, FeedSuccessor>>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,244 @@
+use crate::error::{FeedError, NodeRegistryError, RoundError};
+use crate::events::{
+    AnswerPublished, AnswerRejectedOutlier, ObservationSubmitted, RoundFinalized, RoundStarted,
+    RoundSuperseded,
+};
+use crate::instructions::publish_answer::emit_subscription_warnings;
+use crate::state::{
+    record_history, AnswerReport, Feed, NodeRegistry, ProtocolConfig, Round, RoundSubmission,
+};
+use anchor_lang::prelude::*;
+
+pub fn submit_observation(
+    ctx: Context<SubmitObservation>,
+    round_id: u64,
+    report: AnswerReport,
+) -> Result<()> {
+    let feed_key = ctx.accounts.feed.key();
+    let node = ctx.accounts.node.key();
+    let feed = &mut ctx.accounts.feed.load_mut()?;
+    let round = &mut ctx.accounts.round;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        ctx.accounts.node_registry.nodes.contains(&node),
+        NodeRegistryError::NodeNotFound
+    );
+    require!(feed.status().accepts_answers(), FeedError::FeedRetired);
+    require!(report.has_valid_window(), FeedError::InvalidObservationWindow);
+    require!(
+        feed.value_type()
+            .is_valid(&report.value, report.exponent, report.confidence),
+        FeedError::InvalidValue
+    );
+    require!(report.observed_to <= now, FeedError::FutureTimestamp);
+
+    if round.feed == Pubkey::default() {
+        // First observation opens the round
+        require!(
+            round_id == feed.reporting_round_id + 1,
+            RoundError::InvalidRoundId
+        );
+        require!(feed.can_start_round(now), RoundError::RoundInProgress);
+        require!(
+            feed.time_since_latest_answer(now).unwrap_or(i64::MAX)
+                >= feed.min_update_interval as i64,
+            FeedError::UpdateTooFrequent
+        );
+
+        if feed.reporting_round_id > feed.latest_round_id {
+            emit!(RoundSuperseded {
+                feed: feed_key,
+                round_id: feed.reporting_round_id,
+                superseded_by: round_id,
+                superseded_at: now,
+            });
+        }
+
+        round.feed = feed_key;
+        round.round_id = round_id;
+        round.opened_by = node;
+        round.started_at = now;
+        // Fix the quorums to the room allocated for this round
+        let max_submissions = feed.max_round_submissions(ctx.accounts.node_registry.nodes.len());
+        round.threshold = feed.min_signatures_threshold;
+        round.max_submissions = max_submissions;
+        round.outlier_quorum = feed
+            .outlier_quorum(ctx.accounts.node_registry.nodes.len())
+            .min(max_submissions as usize) as u8;
+        round.bump = ctx.bumps.round;
+
+        feed.reporting_round_id = round_id;
+        feed.reporting_round_started_at = now;
+
+        emit!(RoundStarted {
+            feed: feed_key,
+            round_id,
+            started_by: node,
+            started_at: now,
+        });
+    } else {
+        require!(
+            round_id == feed.reporting_round_id,
+            RoundError::RoundSuperseded
+        );
+        require!(!round.finalized, RoundError::RoundAlreadyFinalized);
+        require!(
+            !round.is_timed_out(now, feed.round_timeout()),
+            RoundError::RoundTimedOut
+        );
+        require!(!round.has_submitted(&node), RoundError::DuplicateSubmission);
+        require!(!round.is_full(), RoundError::RoundFull);
+        require!(
+            round.exponent() == Some(report.exponent),
+            RoundError::ExponentMismatch
+        );
+        // Values without a numeric view cannot be aggregated, nodes must agree
+        require!(
+            feed.value_type().is_numeric() || round.value() == Some(report.value),
+            RoundError::ValueMismatch
+        );
+    }
+
+    round.submissions.push(RoundSubmission { node, report });
+
+    emit!(ObservationSubmitted {
+        feed: feed_key,
+        round_id,
+        node,
+        report,
+        submissions_count: round.submissions.len() as u8,
+        submitted_at: now,
+    });
+
+    if !round.has_quorum() {
+        return Ok(());
+    }
+
+    // Quorum reached: finalize the round into the feed
+    let answer = round.aggregate(feed.value_type());
+    require!(
+        answer.timestamp() > feed.latest_answer.timestamp(),
+        FeedError::PastTimestamp
+    );
+    feed.check_update_policy(&answer)?;
+
+    // Outliers stay open until the elevated quorum has submitted
+    let outlier_quorum = round.outlier_quorum as usize;
+    if feed.is_outlier(&answer) && round.submissions.len() < outlier_quorum {
+        emit!(AnswerRejectedOutlier {
+            feed: feed_key,
+            answer,
+            deviation_bps: answer.deviation_bps(&feed.latest_answer(), feed.value_type()),
+            signatures_count: round.submissions.len() as u8,
+            required_signatures: outlier_quorum as u8,
+            rejected_at: now,
+        });
+        return Ok(());
+    }
+
+    round.finalized = true;
+    round.answer = answer;
+    feed.push_answer(answer);
+    record_history(feed, ctx.accounts.history.as_deref())?;
+    emit_subscription_warnings(feed_key, feed, &ctx.accounts.protocol_config, now);
+
+    msg!(
+        "Round {} finalized with {} submissions",
+        round_id,
+        round.submissions.len()
+    );
+
+    emit!(RoundFinalized {
+        feed: feed_key,
+        round_id,
+        answer,
+        submissions_count: round.submissions.len() as u8,
+        finalized_at: now,
+    });
+
+    emit!(AnswerPublished {
+        feed: feed_key,
+        answer,
+        signatures_count: round.submissions.len() as u8,
+        published_at: now,
+    });
+
+    Ok(())
+}
+
+pub fn close_round(ctx: Context<CloseRound>) -> Result<()> {
+    let feed = &ctx.accounts.feed.load()?;
+    let round = &ctx.accounts.round;
+    let now = Clock::get()?.unix_timestamp;
+
+    // Rounds can be closed once they no longer accept submissions
+    require!(
+        round.finalized
+            || round.round_id < feed.reporting_round_id
+            || round.is_timed_out(now, feed.round_timeout()),
+        RoundError::RoundStillOpen
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct SubmitObservation<'info> {
+    #[account(
+        mut,
+        constraint = feed.load()?.accepts_publishes(Clock::get()?.unix_timestamp, protocol_config.grace_period) @ FeedError::SubscriptionExpired,
+    )]
+    pub feed: AccountLoader<'info, Feed>,
+
+    /// CHECK: The feed's history account, required when it has one. Checked in `record_history`.
+    #[account(mut)]
+    pub history: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        seeds = [NodeRegistry::SEED_PREFIX],
+        bump
+    )]
+    pub node_registry: Account<'info, NodeRegistry>,
+
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = node,
+        space = Round::space(feed.load()?.max_round_submissions(node_registry.nodes.len())),
+        seeds = [Round::SEED_PREFIX, feed.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub round: Account<'info, Round>,
+
+    /// The registered node submitting its observation
+    #[account(mut)]
+    pub node: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseRound<'info> {
+    pub feed: AccountLoader<'info, Feed>,
+
+    #[account(
+        mut,
+        close = opened_by,
+        has_one = feed,
+        has_one = opened_by,
+        seeds = [Round::SEED_PREFIX, feed.key().as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
+    /// CHECK: Receives the round rent, verified against `round.opened_by`
+    #[account(mut)]
+    pub opened_by: UncheckedAccount<'info>,
+}
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked},
+};
+use crate::error::FeedError;
+use crate::events::FeedSubscribed;
+use crate::state::{Feed, FeedType, ProtocolConfig, Subscription};
+
+/// Buys `duration_seconds` of access to a public feed at the feed's price.
+/// The payment joins the feed's pooled balance and keeps the feed live at
+/// least until the subscription's due time through `Feed::subscribed_until`.
+/// It does not extend the authority's `subscription_due_time`, so it is never
+/// part of `close_feed`'s refund.
+pub fn subscribe_to_feed(ctx: Context<SubscribeToFeed>, duration_seconds: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let feed = &mut ctx.accounts.feed.load_mut()?;
+
+    require!(feed.feed_type() == FeedType::Public, FeedError::NotSupported);
+    require!(feed.status().accepts_answers(), FeedError::FeedRetired);
+    require!(
+        duration_seconds >= 86400, // At least 1 day
+        FeedError::MinimumExtensionTime
+    );
+
+    let cost = feed.subscription_cost(duration_seconds)?;
+
+    let decimals = ctx.accounts.underlying_token.decimals;
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.subscriber_token_account.to_account_info(),
+        to: ctx.accounts.program_token_account.to_account_info(),
+        authority: ctx.accounts.subscriber.to_account_info(),
+        mint: ctx.accounts.underlying_token.to_account_info(),
+    };
+    let cpi_context = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    transfer_checked(cpi_context, cost, decimals)?;
+
+    let subscription = &mut ctx.accounts.subscription;
+    if subscription.feed == Pubkey::default() {
+        subscription.feed = ctx.accounts.feed.key();
+        subscription.subscriber = ctx.accounts.subscriber.key();
+        subscription.created_at = now;
+        subscription.bump = ctx.bumps.subscription;
+    }
+    subscription.due_time = i64::try_from(duration_seconds)
+        .ok()
+        .and_then(|duration| subscription.due_time.max(now).checked_add(duration))
+        .ok_or(FeedError::ArithmeticOverflow)?;
+    subscription.total_paid = subscription
+        .total_paid
+        .checked_add(cost)
+        .ok_or(FeedError::ArithmeticOverflow)?;
+
+    feed.add_subscriber_time(subscription.due_time, cost)?;
+
+    emit!(FeedSubscribed {
+        feed: subscription.feed,
+        subscriber: subscription.subscriber,
+        subscription: subscription.key(),
+        duration: duration_seconds,
+        cost,
+        new_due_time: subscription.due_time,
+        subscribed_at: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SubscribeToFeed<'info> {
+    #[account(mut)]
+    pub feed: AccountLoader<'info, Feed>,
+
+    #[account(
+        init_if_needed,
+        payer = subscriber,
+        space = Subscription::SPACE,
+        seeds = [Subscription::SEED_PREFIX, feed.key().as_ref(), subscriber.key().as_ref()],
+        bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(mut)]
+    pub subscriber: Signer<'info>,
+
+    /// Subscriber's associated token account to transfer tokens from
+    #[account(
+        mut,
+        associated_token::mint = underlying_token,
+        associated_token::authority = subscriber,
+    )]
+    pub subscriber_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Program's associated token account to receive tokens
+    #[account(
+        mut,
+        associated_token::mint = underlying_token,
+        associated_token::authority = protocol_config,
+    )]
+    pub program_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// The underlying token mint
+    #[account(address = protocol_config.underlying_token)]
+    pub underlying_token: InterfaceAccount<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
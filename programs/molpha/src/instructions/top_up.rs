@@ -12,14 +12,15 @@ pub fn top_up(ctx: Context<TopUp>, amount: u64) -> Result<()> {
     );
     anchor_lang::system_program::transfer(cpi_context, amount)?;
 
-    ctx.accounts.feed.balance += amount;
+    let feed = &mut ctx.accounts.feed.load_mut()?;
+    feed.balance += amount;
 
     // Emit event
     emit!(FeedToppedUp {
         feed: ctx.accounts.feed.key(),
         authority: ctx.accounts.authority.key(),
         amount,
-        new_balance: ctx.accounts.feed.balance,
+        new_balance: feed.balance,
         topped_up_at: Clock::get()?.unix_timestamp,
     });
 
@@ -32,7 +33,7 @@ pub struct TopUp<'info> {
         mut,
         has_one = authority
     )]
-    pub feed: Account<'info, Feed>,
+    pub feed: AccountLoader<'info, Feed>,
     #[account(mut)]
     pub authority: Signer<'info>,
     pub system_program: Program<'info, System>,
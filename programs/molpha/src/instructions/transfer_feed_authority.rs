@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::error::FeedError;
+use crate::events::{FeedAuthorityTransferProposed, FeedAuthorityTransferred};
+use crate::state::Feed;
+
+/// First step of an ownership transfer: the current authority proposes a new
+/// one. Proposing `Pubkey::default()` cancels a pending transfer.
+pub fn propose_feed_authority(
+    ctx: Context<ProposeFeedAuthority>,
+    new_authority: Pubkey,
+) -> Result<()> {
+    ctx.accounts.feed.load_mut()?.pending_authority = new_authority;
+
+    emit!(FeedAuthorityTransferProposed {
+        feed: ctx.accounts.feed.key(),
+        authority: ctx.accounts.authority.key(),
+        pending_authority: new_authority,
+        proposed_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Second step: the proposed authority accepts and takes over the feed. The
+/// feed address does not change.
+pub fn accept_feed_authority(ctx: Context<AcceptFeedAuthority>) -> Result<()> {
+    let feed = &mut ctx.accounts.feed.load_mut()?;
+    let previous_authority = feed.authority;
+    feed.authority = ctx.accounts.new_authority.key();
+    feed.pending_authority = Pubkey::default();
+
+    emit!(FeedAuthorityTransferred {
+        feed: ctx.accounts.feed.key(),
+        previous_authority,
+        new_authority: feed.authority,
+        transferred_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ProposeFeedAuthority<'info> {
+    #[account(
+        mut,
+        has_one = authority
+    )]
+    pub feed: AccountLoader<'info, Feed>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptFeedAuthority<'info> {
+    #[account(
+        mut,
+        constraint = feed.load()?.pending_authority == new_authority.key() @ FeedError::NotPendingAuthority
+    )]
+    pub feed: AccountLoader<'info, Feed>,
+    pub new_authority: Signer<'info>,
+}
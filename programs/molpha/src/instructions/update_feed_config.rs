@@ -1,6 +1,6 @@
 use crate::error::FeedError;
 use crate::events::FeedConfigUpdated;
-use crate::state::{Feed, FeedType, ProtocolConfig};
+use crate::state::{Feed, FeedType, ProtocolConfig, UpdatePolicy};
 use crate::utils::pricing::calculate_price_per_second_scaled;
 use anchor_lang::prelude::*;
 
@@ -8,10 +8,10 @@ pub fn update_feed_config(
     ctx: Context<UpdateFeedConfig>,
     params: UpdateFeedConfigParams,
 ) -> Result<()> {
-    let feed = &mut ctx.accounts.feed;
+    let feed = &mut ctx.accounts.feed.load_mut()?;
 
     require!(
-        feed.feed_type == FeedType::Personal,
+        feed.feed_type() == FeedType::Personal,
         FeedError::NotSupported
     );
     require!(!params.ipfs_cid.is_empty(), FeedError::InvalidFeedConfig);
@@ -19,17 +19,30 @@ pub fn update_feed_config(
         params.min_signatures_threshold > 0,
         FeedError::InvalidFeedConfig
     );
+    require!(params.update_policy.is_valid(), FeedError::InvalidFeedConfig);
+    require!(params.frequency > 0, FeedError::InvalidFeedConfig);
 
     let old_price_per_second_scaled = feed.price_per_second_scaled;
     let price_per_second_scaled = calculate_price_per_second_scaled(feed, &ctx.accounts.protocol_config)?;
-    let time_left = feed.subscription_due_time as u64 - Clock::get()?.unix_timestamp as u64;
-    let new_due_time = Clock::get()?.unix_timestamp as u64 + ((time_left * old_price_per_second_scaled) / price_per_second_scaled);
 
-    feed.subscription_due_time = new_due_time as i64;
+    // Rescale the remaining time to the new price. A subscription that has
+    // already expired, e.g. during its grace period, keeps its due time.
+    let now = Clock::get()?.unix_timestamp;
+    let time_left = feed.remaining_subscription_time(now);
+    if time_left > 0 && price_per_second_scaled > 0 {
+        let new_time_left = time_left as u128 * old_price_per_second_scaled as u128
+            / price_per_second_scaled as u128;
+        feed.subscription_due_time = i64::try_from(new_time_left)
+            .ok()
+            .and_then(|time_left| now.checked_add(time_left))
+            .ok_or(FeedError::ArithmeticOverflow)?;
+    }
+
     feed.price_per_second_scaled = price_per_second_scaled;
     feed.min_signatures_threshold = params.min_signatures_threshold;
     feed.frequency = params.frequency;
-    feed.ipfs_cid = params.ipfs_cid.clone();
+    feed.set_update_policy(&params.update_policy);
+    feed.set_ipfs_cid(&params.ipfs_cid)?;
     feed.job_id = params.job_id;
 
     // Emit event
@@ -37,7 +50,7 @@ pub fn update_feed_config(
         feed: ctx.accounts.feed.key(),
         authority: ctx.accounts.authority.key(),
         new_ipfs_cid: params.ipfs_cid,
-        updated_at: Clock::get()?.unix_timestamp,
+        updated_at: now,
     });
 
     Ok(())
@@ -49,7 +62,7 @@ pub struct UpdateFeedConfig<'info> {
         mut,
         has_one = authority
     )]
-    pub feed: Account<'info, Feed>,
+    pub feed: AccountLoader<'info, Feed>,
     pub authority: Signer<'info>,
     #[account(
         seeds = [ProtocolConfig::SEED_PREFIX],
@@ -62,6 +75,7 @@ pub struct UpdateFeedConfig<'info> {
 pub struct UpdateFeedConfigParams {
     pub min_signatures_threshold: u8,
     pub frequency: u64,
+    pub update_policy: UpdatePolicy,
     pub ipfs_cid: String,
     pub job_id: [u8; 32],
 }
@@ -0,0 +1,68 @@
+use crate::error::ProtocolError;
+use crate::events::ProtocolConfigUpdated;
+use crate::state::ProtocolConfig;
+use anchor_lang::prelude::*;
+
+pub fn update_protocol_config(
+    ctx: Context<UpdateProtocolConfig>,
+    params: UpdateProtocolConfigParams,
+) -> Result<()> {
+    require!(
+        params.transmitter_window == 0
+            || params.transmitter_fallback_delay < params.transmitter_window,
+        ProtocolError::InvalidProtocolConfig
+    );
+    require!(params.crank_tip_bps <= 10_000, ProtocolError::InvalidProtocolConfig);
+
+    let config = &mut ctx.accounts.protocol_config;
+    config.base_price_per_second_scaled = params.base_price_per_second_scaled;
+    config.frequency_coefficient = params.frequency_coefficient;
+    config.signers_coefficient = params.signers_coefficient;
+    config.reward_percentage = params.reward_percentage;
+    config.priority_fee_buffer_percentage = params.priority_fee_buffer_percentage;
+    config.max_priority_fee_coverage = params.max_priority_fee_coverage;
+    config.priority_fee_smoothing_window = params.priority_fee_smoothing_window;
+    config.transmitter_window = params.transmitter_window;
+    config.transmitter_fallback_delay = params.transmitter_fallback_delay;
+    config.crank_tip_bps = params.crank_tip_bps;
+    config.grace_period = params.grace_period;
+    config.low_subscription_threshold = params.low_subscription_threshold;
+    config.low_priority_budget_threshold = params.low_priority_budget_threshold;
+
+    emit!(ProtocolConfigUpdated {
+        protocol_config: config.key(),
+        authority: ctx.accounts.authority.key(),
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateProtocolConfig<'info> {
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump,
+        has_one = authority
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateProtocolConfigParams {
+    pub base_price_per_second_scaled: u64,
+    pub frequency_coefficient: u64,
+    pub signers_coefficient: u64,
+    pub reward_percentage: u64,
+    pub priority_fee_buffer_percentage: u16,
+    pub max_priority_fee_coverage: u64,
+    pub priority_fee_smoothing_window: u8,
+    pub transmitter_window: u64,
+    pub transmitter_fallback_delay: u64,
+    pub crank_tip_bps: u16,
+    pub grace_period: u64,
+    pub low_subscription_threshold: u64,
+    pub low_priority_budget_threshold: u64,
+}
@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked},
+};
+
+use crate::error::FeedError;
+use crate::events::ReportVerified;
+use crate::state::{AnswerReport, AnswerV2, Feed, FeedType, NodeRegistry, PriceUpdate, ProtocolConfig};
+use crate::utils::merkle::{compute_root, leaf_hash};
+use crate::utils::{collect_node_signers, pricing::*};
+
+/// Verifies a node-signed report for `feed` inside the consumer's transaction
+/// (pull mode) and returns the answer as return data. Nodes sign the Merkle
+/// root over (feed, report) leaves, so a report from a `publish_batch` root can
+/// be pulled with its proof; a single report has an empty proof.
+/// Reports observed more than `max_age_seconds` ago, or before the feed's
+/// latest answer, are rejected, as are reports for paused or retired feeds.
+/// The consumer pays one update period of the feed's price; the feed's
+/// subscription is left untouched.
+pub fn verify_report(
+    ctx: Context<VerifyReport>,
+    report: AnswerReport,
+    proof: Vec<[u8; 32]>,
+    max_age_seconds: u64,
+) -> Result<AnswerV2> {
+    let feed_key = ctx.accounts.feed.key();
+    let feed = &ctx.accounts.feed.load()?;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        feed.feed_type() == FeedType::Public || feed.authority == ctx.accounts.consumer.key(),
+        FeedError::ReaderNotAllowed
+    );
+    feed.check_readable()?;
+    require!(report.has_valid_window(), FeedError::InvalidObservationWindow);
+    require!(
+        feed.value_type()
+            .is_valid(&report.value, report.exponent, report.confidence),
+        FeedError::InvalidValue
+    );
+    require!(report.observed_to <= now, FeedError::FutureTimestamp);
+    require!(
+        now - report.observed_to <= i64::try_from(max_age_seconds).unwrap_or(i64::MAX),
+        FeedError::StaleAnswer
+    );
+    require!(
+        report.observed_to >= feed.latest_answer.timestamp(),
+        FeedError::PastTimestamp
+    );
+
+    let root = compute_root(leaf_hash(&feed_key, &report), &proof);
+    let unique_valid_signers = collect_node_signers(
+        &ctx.accounts.instructions,
+        &root,
+        &ctx.accounts.node_registry.nodes,
+    )?;
+    require!(
+        unique_valid_signers.len() >= feed.min_signatures_threshold as usize,
+        FeedError::NotEnoughSignatures
+    );
+
+    let answer = AnswerV2::from_report(&report, 0, unique_valid_signers.len() as u8);
+
+    // Bill the consumer instead of the feed subscription
+    let fee = calculate_pull_fee(feed)?;
+    if fee > 0 {
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.consumer_token_account.to_account_info(),
+            to: ctx.accounts.program_token_account.to_account_info(),
+            authority: ctx.accounts.consumer.to_account_info(),
+            mint: ctx.accounts.underlying_token.to_account_info(),
+        };
+        let cpi_context = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        transfer_checked(cpi_context, fee, ctx.accounts.underlying_token.decimals)?;
+    }
+
+    if let Some(price_update) = ctx.accounts.price_update.as_mut() {
+        require!(
+            answer.timestamp() > price_update.answer.timestamp(),
+            FeedError::PastTimestamp
+        );
+        price_update.feed = feed_key;
+        price_update.consumer = ctx.accounts.consumer.key();
+        price_update.answer = answer;
+        price_update.posted_at = now;
+        price_update.bump = ctx.bumps.price_update.unwrap_or_default();
+    }
+
+    emit!(ReportVerified {
+        feed: feed_key,
+        consumer: ctx.accounts.consumer.key(),
+        answer,
+        fee,
+        verified_at: now,
+    });
+
+    Ok(answer)
+}
+
+#[derive(Accounts)]
+pub struct VerifyReport<'info> {
+    pub feed: AccountLoader<'info, Feed>,
+
+    #[account(
+        seeds = [NodeRegistry::SEED_PREFIX],
+        bump
+    )]
+    pub node_registry: Account<'info, NodeRegistry>,
+
+    /// Optional storage for the verified answer, owned by the consumer
+    #[account(
+        init_if_needed,
+        payer = consumer,
+        space = PriceUpdate::SPACE,
+        seeds = [PriceUpdate::SEED_PREFIX, feed.key().as_ref(), consumer.key().as_ref()],
+        bump
+    )]
+    pub price_update: Option<Account<'info, PriceUpdate>>,
+
+    /// The consumer pulling the report, a PDA signer when called by CPI
+    #[account(mut)]
+    pub consumer: Signer<'info>,
+
+    /// Consumer's payment account for the pull fee
+    #[account(
+        mut,
+        token::mint = underlying_token,
+        token::authority = consumer,
+    )]
+    pub consumer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Program's associated token account to receive tokens
+    #[account(
+        mut,
+        associated_token::mint = underlying_token,
+        associated_token::authority = protocol_config,
+    )]
+    pub program_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// The underlying token mint
+    #[account(address = protocol_config.underlying_token)]
+    pub underlying_token: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: This is safe. We only read the instructions sysvar for validation.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+#[cfg(feature = "cpi")]
+pub mod consumer;
 pub mod error;
 pub mod events;
 pub mod instructions;
@@ -20,6 +22,17 @@ pub mod molpha {
         instructions::initialize(ctx)
     }
 
+    pub fn update_protocol_config(
+        ctx: Context<UpdateProtocolConfig>,
+        params: UpdateProtocolConfigParams,
+    ) -> Result<()> {
+        instructions::update_protocol_config(ctx, params)
+    }
+
+    pub fn migrate_protocol_config(ctx: Context<MigrateProtocolConfig>) -> Result<()> {
+        instructions::migrate_protocol_config(ctx)
+    }
+
     pub fn add_node(ctx: Context<AddNode>, node_pubkey: Pubkey) -> Result<()> {
         instructions::add_node(ctx, node_pubkey)
     }
@@ -46,6 +59,19 @@ pub mod molpha {
         instructions::extend_subscription(ctx, additional_duration_seconds, additional_priority_fee_budget)
     }
 
+    pub fn set_auto_renew(
+        ctx: Context<SetAutoRenew>,
+        duration: u64,
+        priority_budget: u64,
+        window: u64,
+    ) -> Result<()> {
+        instructions::set_auto_renew(ctx, duration, priority_budget, window)
+    }
+
+    pub fn crank_renew(ctx: Context<CrankRenew>) -> Result<()> {
+        instructions::crank_renew(ctx)
+    }
+
     pub fn update_feed_config(
         ctx: Context<UpdateFeedConfig>,
         params: UpdateFeedConfigParams,
@@ -53,8 +79,162 @@ pub mod molpha {
         instructions::update_feed_config(ctx, params)
     }
 
-    pub fn publish_answer(ctx: Context<PublishAnswer>, answer: Answer) -> Result<()> {
-        instructions::publish_answer(ctx, answer)
+    pub fn publish_answer(ctx: Context<PublishAnswer>, report: AnswerReport) -> Result<()> {
+        instructions::publish_answer(ctx, report)
+    }
+
+    pub fn publish_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PublishBatch<'info>>,
+        root: [u8; 32],
+        leaves: Vec<BatchLeaf>,
+    ) -> Result<()> {
+        instructions::publish_batch(ctx, root, leaves)
+    }
+
+    pub fn verify_report(
+        ctx: Context<VerifyReport>,
+        report: AnswerReport,
+        proof: Vec<[u8; 32]>,
+        max_age_seconds: u64,
+    ) -> Result<AnswerV2> {
+        instructions::verify_report(ctx, report, proof, max_age_seconds)
+    }
+
+    pub fn read_answer(
+        ctx: Context<ReadAnswer>,
+        max_age_seconds: u64,
+        min_signers: u8,
+    ) -> Result<AnswerV2> {
+        instructions::read_answer(ctx, max_age_seconds, min_signers)
+    }
+
+    pub fn get_answer_at(ctx: Context<ReadHistory>, timestamp: i64) -> Result<HistoricalAnswer> {
+        instructions::get_answer_at(ctx, timestamp)
+    }
+
+    pub fn get_round(ctx: Context<ReadHistory>, round_id: u64) -> Result<HistoricalAnswer> {
+        instructions::get_round(ctx, round_id)
+    }
+
+    pub fn get_twap(ctx: Context<ReadHistory>, window_seconds: u64) -> Result<AverageAnswer> {
+        instructions::get_twap(ctx, window_seconds)
+    }
+
+    pub fn read_derived_answer(
+        ctx: Context<ReadDerivedAnswer>,
+        max_age_seconds: u64,
+        min_signers: u8,
+    ) -> Result<AnswerV2> {
+        instructions::read_derived_answer(ctx, max_age_seconds, min_signers)
+    }
+
+    pub fn read_bundle_slot(
+        ctx: Context<ReadBundleSlot>,
+        index: u16,
+        max_age_seconds: u64,
+        min_signers: u8,
+    ) -> Result<AnswerV2> {
+        instructions::read_bundle_slot(ctx, index, max_age_seconds, min_signers)
+    }
+
+    pub fn read_answer_gated(
+        ctx: Context<ReadAnswerGated>,
+        max_age_seconds: u64,
+        min_signers: u8,
+    ) -> Result<AnswerV2> {
+        instructions::read_answer_gated(ctx, max_age_seconds, min_signers)
+    }
+
+    pub fn read_subscribed_answer(
+        ctx: Context<ReadSubscribedAnswer>,
+        max_age_seconds: u64,
+        min_signers: u8,
+    ) -> Result<AnswerV2> {
+        instructions::read_subscribed_answer(ctx, max_age_seconds, min_signers)
+    }
+
+    pub fn set_feed_readers(ctx: Context<SetFeedReaders>, readers: Vec<Pubkey>) -> Result<()> {
+        instructions::set_feed_readers(ctx, readers)
+    }
+
+    pub fn set_feed_status(ctx: Context<SetFeedStatus>, status: FeedStatus) -> Result<()> {
+        instructions::set_feed_status(ctx, status)
+    }
+
+    pub fn propose_feed_authority(
+        ctx: Context<ProposeFeedAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::propose_feed_authority(ctx, new_authority)
+    }
+
+    pub fn accept_feed_authority(ctx: Context<AcceptFeedAuthority>) -> Result<()> {
+        instructions::accept_feed_authority(ctx)
+    }
+
+    pub fn create_derived_feed(
+        ctx: Context<CreateDerivedFeed>,
+        params: CreateDerivedFeedParams,
+    ) -> Result<()> {
+        instructions::create_derived_feed(ctx, params)
+    }
+
+    pub fn refresh_derived<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RefreshDerived<'info>>,
+    ) -> Result<()> {
+        instructions::refresh_derived(ctx)
+    }
+
+    pub fn create_feed_bundle(
+        ctx: Context<CreateFeedBundle>,
+        params: CreateFeedBundleParams,
+    ) -> Result<()> {
+        instructions::create_feed_bundle(ctx, params)
+    }
+
+    pub fn add_bundle_slots(ctx: Context<AddBundleSlots>, names: Vec<String>) -> Result<()> {
+        instructions::add_bundle_slots(ctx, names)
+    }
+
+    pub fn publish_bundle(ctx: Context<PublishBundle>, report: BundleReport) -> Result<()> {
+        instructions::publish_bundle(ctx, report)
+    }
+
+    pub fn create_feed_history(ctx: Context<CreateFeedHistory>, capacity: u32) -> Result<()> {
+        instructions::create_feed_history(ctx, capacity)
+    }
+
+    pub fn grow_feed_history(ctx: Context<GrowFeedHistory>, new_capacity: u32) -> Result<()> {
+        instructions::grow_feed_history(ctx, new_capacity)
+    }
+
+    pub fn migrate_feed(ctx: Context<MigrateFeed>) -> Result<()> {
+        instructions::migrate_feed(ctx)
+    }
+
+    pub fn migrate_feed_address(ctx: Context<MigrateFeedAddress>, name: String) -> Result<()> {
+        instructions::migrate_feed_address(ctx, name)
+    }
+
+    // Multi-transaction rounds
+    pub fn submit_observation(
+        ctx: Context<SubmitObservation>,
+        round_id: u64,
+        report: AnswerReport,
+    ) -> Result<()> {
+        instructions::submit_observation(ctx, round_id, report)
+    }
+
+    pub fn close_round(ctx: Context<CloseRound>) -> Result<()> {
+        instructions::close_round(ctx)
+    }
+
+    pub fn subscribe_to_feed(ctx: Context<SubscribeToFeed>, duration_seconds: u64) -> Result<()> {
+        instructions::subscribe_to_feed(ctx, duration_seconds)
+    }
+
+    pub fn close_feed(ctx: Context<CloseFeed>) -> Result<()> {
+        instructions::close_feed(ctx)
     }
 
     pub fn top_up(ctx: Context<TopUp>, amount: u64) -> Result<()> {
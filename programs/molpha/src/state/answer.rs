@@ -1,5 +1,8 @@
+use super::{unsigned_deviation_bps, ValueType};
 use anchor_lang::prelude::*;
 
+/// Original answer format: an opaque value and its timestamp.
+/// Only used to read feeds created before `AnswerV2`.
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
 pub struct Answer {
     pub value: [u8; 32],
@@ -9,3 +12,148 @@ pub struct Answer {
 impl Answer {
     pub const SPACE: usize = Answer::INIT_SPACE;
 }
+
+/// Observation signed by the nodes. The program assigns the round ID and
+/// signer count when it turns a report into an `AnswerV2`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct AnswerReport {
+    pub value: [u8; 32],
+    pub exponent: i32,
+    pub confidence: u128,
+    pub observed_from: i64,
+    pub observed_to: i64,
+}
+
+impl AnswerReport {
+    /// Message the nodes sign for `feed`: the feed address followed by the
+    /// Borsh encoding of the report, so that a report only publishes to the
+    /// feed it was signed for.
+    pub fn signing_message(&self, feed: &Pubkey) -> Vec<u8> {
+        [feed.as_ref(), &borsh::to_vec(self).unwrap()].concat()
+    }
+
+    pub fn has_valid_window(&self) -> bool {
+        self.observed_from <= self.observed_to
+    }
+}
+
+/// Answer stored on feeds. The value is encoded according to the feed's
+/// `ValueType`; numeric values read as `value * 10^exponent`, with a
+/// confidence interval in the same scale. The observation window bounds
+/// when the value was observed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct AnswerV2 {
+    pub round_id: u64,
+    pub value: [u8; 32],
+    pub exponent: i32,
+    pub confidence: u128,
+    pub observed_from: i64,
+    pub observed_to: i64,
+    pub signer_count: u8,
+}
+
+impl AnswerV2 {
+    pub const SPACE: usize = AnswerV2::INIT_SPACE;
+
+    pub fn from_report(report: &AnswerReport, round_id: u64, signer_count: u8) -> Self {
+        Self {
+            round_id,
+            value: report.value,
+            exponent: report.exponent,
+            confidence: report.confidence,
+            observed_from: report.observed_from,
+            observed_to: report.observed_to,
+            signer_count,
+        }
+    }
+
+    /// Converts a legacy answer, observed at its timestamp.
+    pub fn from_legacy(answer: &Answer, round_id: u64) -> Self {
+        Self {
+            round_id,
+            value: answer.value,
+            observed_from: answer.timestamp,
+            observed_to: answer.timestamp,
+            ..Default::default()
+        }
+    }
+
+    /// Time the answer is effective from.
+    pub fn timestamp(&self) -> i64 {
+        self.observed_to
+    }
+
+    /// Deviation of this answer from `previous` in basis points, saturating at `u64::MAX`.
+    /// A change of exponent, any change away from zero, or any change of a value
+    /// without a numeric view counts as a full deviation.
+    pub fn deviation_bps(&self, previous: &AnswerV2, value_type: ValueType) -> u64 {
+        if self.value == previous.value && self.exponent == previous.exponent {
+            return 0;
+        }
+        if self.exponent != previous.exponent {
+            return u64::MAX;
+        }
+
+        if value_type == ValueType::U256 {
+            return unsigned_deviation_bps(&self.value, &previous.value);
+        }
+        match (value_type.as_i128(&self.value), value_type.as_i128(&previous.value)) {
+            (Some(current), Some(previous)) if previous != 0 => current
+                .abs_diff(previous)
+                .checked_mul(10_000)
+                .map(|scaled| u64::try_from(scaled / previous.unsigned_abs()).unwrap_or(u64::MAX))
+                .unwrap_or(u64::MAX),
+            _ => u64::MAX,
+        }
+    }
+}
+
+/// Fixed-layout `AnswerV2` for zero-copy accounts. The confidence is stored as
+/// little-endian bytes so that the record has no padding and 8-byte alignment.
+#[zero_copy]
+#[derive(Default)]
+pub struct AnswerRecord {
+    pub round_id: u64,
+    pub observed_from: i64,
+    pub observed_to: i64,
+    pub value: [u8; 32],
+    pub confidence: [u8; 16],
+    pub exponent: i32,
+    pub signer_count: u8,
+    pub _padding: [u8; 3],
+}
+
+impl AnswerRecord {
+    pub fn timestamp(&self) -> i64 {
+        self.observed_to
+    }
+}
+
+impl From<AnswerV2> for AnswerRecord {
+    fn from(answer: AnswerV2) -> Self {
+        Self {
+            round_id: answer.round_id,
+            observed_from: answer.observed_from,
+            observed_to: answer.observed_to,
+            value: answer.value,
+            confidence: answer.confidence.to_le_bytes(),
+            exponent: answer.exponent,
+            signer_count: answer.signer_count,
+            _padding: [0; 3],
+        }
+    }
+}
+
+impl From<AnswerRecord> for AnswerV2 {
+    fn from(record: AnswerRecord) -> Self {
+        Self {
+            round_id: record.round_id,
+            value: record.value,
+            exponent: record.exponent,
+            confidence: u128::from_le_bytes(record.confidence),
+            observed_from: record.observed_from,
+            observed_to: record.observed_to,
+            signer_count: record.signer_count,
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use super::Feed;
+use anchor_lang::prelude::*;
+
+/// Auto-renew settings of a feed (`set_auto_renew`), applied by `crank_renew`.
+#[account]
+#[derive(Default, InitSpace)]
+pub struct AutoRenew {
+    pub feed: Pubkey,
+    pub payer: Pubkey,        // Authority that configured auto-renew and pays for renewals
+    pub duration: u64,        // Seconds added per renewal (0 disables auto-renew)
+    pub priority_budget: u64, // Priority fee budget added per renewal
+    pub window: u64,          // Seconds before the due time from which a renewal is allowed, below `duration`
+    pub bump: u8,
+}
+
+impl AutoRenew {
+    pub const SEED_PREFIX: &'static [u8] = b"auto_renew";
+    pub const SPACE: usize = 8 + AutoRenew::INIT_SPACE;
+
+    pub fn is_enabled(&self) -> bool {
+        self.duration > 0
+    }
+
+    /// Whether `crank_renew` can extend the subscription of `feed` at `current_time`.
+    pub fn is_renewal_due(&self, feed: &Feed, current_time: i64) -> bool {
+        self.is_enabled()
+            && current_time
+                >= feed
+                    .subscription_due_time
+                    .saturating_sub(i64::try_from(self.window).unwrap_or(i64::MAX))
+    }
+}
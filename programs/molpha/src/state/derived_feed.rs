@@ -0,0 +1,139 @@
+use super::{AnswerV2, MAX_NAME_LEN};
+use crate::error::{DerivedFeedError, FeedError};
+use anchor_lang::prelude::*;
+
+pub const MIN_DERIVED_SOURCES: usize = 2;
+pub const MAX_DERIVED_SOURCES: usize = 8;
+
+/// How a derived feed combines its sources, each read as `value * 10^exponent`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub enum DerivedExpression {
+    #[default]
+    Ratio,       // sources[0] / sources[1], e.g. ETH/BTC from ETH/USD and BTC/USD
+    Product,     // sources[0] * sources[1]
+    WeightedSum, // Sum of weights[i] * sources[i] / 10_000, e.g. a basket
+}
+
+/// Feed computed on chain from 2 to 8 numeric `Feed`s (`refresh_derived`).
+/// The result is an `I128Price` answer in `exponent`, effective from the
+/// oldest source answer and counting the fewest source signers. Confidence
+/// is not propagated.
+#[account]
+#[derive(Default, InitSpace)]
+pub struct DerivedFeed {
+    pub authority: Pubkey,
+    #[max_len(MAX_NAME_LEN)]
+    pub name: String,
+    pub expression: DerivedExpression,
+    #[max_len(MAX_DERIVED_SOURCES)]
+    pub sources: Vec<Pubkey>,
+    #[max_len(MAX_DERIVED_SOURCES)]
+    pub weights: Vec<i64>, // Basis points, `WeightedSum` only
+    pub exponent: i32,
+    pub max_staleness: u64, // Maximum age of a source answer at refresh, in seconds
+    pub latest_answer: AnswerV2,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl DerivedFeed {
+    pub const SEED_PREFIX: &'static [u8] = b"derived";
+    pub const SPACE: usize = 8 + DerivedFeed::INIT_SPACE;
+
+    /// Whether the sources and weights fit the expression.
+    pub fn is_valid(&self) -> bool {
+        let sources = self.sources.len();
+        let arity_ok = match self.expression {
+            DerivedExpression::Ratio | DerivedExpression::Product => sources == 2,
+            DerivedExpression::WeightedSum => {
+                (MIN_DERIVED_SOURCES..=MAX_DERIVED_SOURCES).contains(&sources)
+            }
+        };
+        let weights_ok = match self.expression {
+            DerivedExpression::WeightedSum => self.weights.len() == sources,
+            _ => self.weights.is_empty(),
+        };
+        arity_ok && weights_ok && self.max_staleness > 0
+    }
+
+    /// Latest answer for consumers, with the same guards as
+    /// `Feed::read_latest_answer`.
+    pub fn read_latest_answer(
+        &self,
+        current_time: i64,
+        max_age_seconds: u64,
+        min_signers: u8,
+    ) -> Result<AnswerV2> {
+        require!(self.latest_answer.round_id != 0, FeedError::NoAnswer);
+        require!(
+            current_time - self.latest_answer.timestamp()
+                <= i64::try_from(max_age_seconds).unwrap_or(i64::MAX),
+            FeedError::StaleAnswer
+        );
+        require!(
+            self.latest_answer.signer_count >= min_signers,
+            FeedError::InsufficientSigners
+        );
+        Ok(self.latest_answer)
+    }
+
+    /// Evaluates the expression over the source values, given as
+    /// `(value, exponent)` in source order, in the feed's exponent.
+    pub fn compute(&self, values: &[(i128, i32)]) -> Result<i128> {
+        match self.expression {
+            DerivedExpression::Ratio => {
+                let ((a, ea), (b, eb)) = (values[0], values[1]);
+                require!(b != 0, DerivedFeedError::DivisionByZero);
+                // a * 10^ea / (b * 10^eb) = (a / b) * 10^(ea - eb - exponent) * 10^exponent
+                let shift = ea as i64 - eb as i64 - self.exponent as i64;
+                if shift >= 0 {
+                    a.checked_mul(pow10(shift)?)
+                        .ok_or(error!(DerivedFeedError::ArithmeticOverflow))
+                        .map(|numerator| numerator / b)
+                } else {
+                    b.checked_mul(pow10(-shift)?)
+                        .ok_or(error!(DerivedFeedError::ArithmeticOverflow))
+                        .map(|denominator| a / denominator)
+                }
+            }
+            DerivedExpression::Product => {
+                let ((a, ea), (b, eb)) = (values[0], values[1]);
+                let product = a
+                    .checked_mul(b)
+                    .ok_or(error!(DerivedFeedError::ArithmeticOverflow))?;
+                rescale(product, ea as i64 + eb as i64, self.exponent)
+            }
+            DerivedExpression::WeightedSum => {
+                let mut sum: i128 = 0;
+                for (&(value, exponent), &weight) in values.iter().zip(&self.weights) {
+                    let term = rescale(value, exponent as i64, self.exponent)?
+                        .checked_mul(weight as i128)
+                        .ok_or(error!(DerivedFeedError::ArithmeticOverflow))?;
+                    sum = sum
+                        .checked_add(term)
+                        .ok_or(error!(DerivedFeedError::ArithmeticOverflow))?;
+                }
+                Ok(sum / 10_000)
+            }
+        }
+    }
+}
+
+/// `value * 10^from` expressed in `10^to` units, truncated.
+fn rescale(value: i128, from: i64, to: i32) -> Result<i128> {
+    let shift = from - to as i64;
+    if shift >= 0 {
+        value
+            .checked_mul(pow10(shift)?)
+            .ok_or(error!(DerivedFeedError::ArithmeticOverflow))
+    } else {
+        Ok(value / pow10(-shift)?)
+    }
+}
+
+fn pow10(exponent: i64) -> Result<i128> {
+    u32::try_from(exponent)
+        .ok()
+        .and_then(|exponent| 10i128.checked_pow(exponent))
+        .ok_or(error!(DerivedFeedError::ArithmeticOverflow))
+}
@@ -1,49 +1,425 @@
-use super::{Answer, FeedType};
+use super::{
+    Answer, AnswerRecord, AnswerV2, FeedStatus, FeedType, HistoryEntry, ProtocolConfig, TypedValue, UpdatePolicy,
+    ValueType,
+};
+use crate::error::FeedError;
 use anchor_lang::prelude::*;
+use bytemuck::Zeroable;
+use std::mem::{offset_of, size_of};
 
-pub const MAX_HISTORY: usize = 20; // Reduced from 100 to 20
+pub const MAX_HISTORY: usize = 20; // History length of the legacy layout
+pub const MAX_NAME_LEN: usize = 32; // The name is a PDA seed, with the creator
+pub const MAX_IPFS_CID_LEN: usize = 64;
 
-#[account]
-#[derive(Default, InitSpace)]
+/// Zero-copy feed account. The hot fields come first, at stable offsets (see
+/// `Feed::LATEST_ANSWER_OFFSET` and the following constants), so that
+/// consumers can read them without Anchor. Strings are zero-padded.
+#[account(zero_copy)]
 pub struct Feed {
-    // Existing feed data
-    #[max_len(64)]
-    pub name: String,
-    pub authority: Pubkey,
-    pub feed_type: FeedType,
-    pub job_id: [u8; 32],
-    pub data_source: Pubkey,
+    // Hot fields
+    pub latest_answer: AnswerRecord,
     pub balance: u64,
+    pub subscription_due_time: i64,
+    pub latest_round_id: u64,            // Last finalized round
     pub min_signatures_threshold: u8,
+    pub status: u8, // `FeedStatus`, see `Feed::status`
+    pub feed_type: u8,  // `FeedType`
+    pub value_type: u8, // `ValueType`, fixed at creation
+    pub priority_fee_samples: u8, // Fees averaged so far, saturating
+    pub bump: u8,
+    pub restricted_readers: u8, // Reads go through `read_answer_gated` (0 or 1), see `FeedReaders`
+    pub paused_by_admin: u8,    // Paused by the protocol admin, who alone can lift it (0 or 1)
+
+    // Configuration
+    pub authority: Pubkey,
+    pub data_source: Pubkey,
+    pub history: Pubkey, // `FeedHistory` account, default if the feed keeps no history
+    pub job_id: [u8; 32],
+    pub name: [u8; MAX_NAME_LEN],
+    pub ipfs_cid: [u8; MAX_IPFS_CID_LEN],
     pub frequency: u64,
-    #[max_len(60)]
-    pub ipfs_cid: String,
-    pub latest_answer: Answer,
-    #[max_len(MAX_HISTORY)]
-    pub answer_history: Vec<Answer>,
-    pub history_idx: u64,
-    
+    // `UpdatePolicy`, see `Feed::update_policy`
+    pub min_update_interval: u64,
+    pub heartbeat: u64,
+    pub deviation_threshold_bps: u16,
+    pub max_deviation_bps: u16,
+    pub outlier_signatures_threshold: u8,
+    pub _policy_padding: [u8; 3],
+
+    // Multi-transaction rounds (see `Round`)
+    pub reporting_round_id: u64,         // Last opened round
+    pub reporting_round_started_at: i64,
+
     // Integrated subscription data (like SubscriptionRegistry)
-    pub subscription_due_time: i64,
     pub price_per_second_scaled: u64,
     pub priority_fee_allowance: u64,
     pub consumed_priority_fees: u64,
+    pub average_priority_fee: u64, // Smoothed metered fee, see `Feed::smoothed_priority_fee`
     pub created_at: i64,
-    pub bump: u8,
+
+    // Averages of numeric answers, see `Feed::accumulate`
+    pub price_cumulative: [u8; 16], // TWAP accumulator at the latest answer (i128, little-endian, wrapping)
+    pub ema: [u8; 16],              // Exponential moving average (i128, little-endian)
+    pub ema_period: u64,            // `UpdatePolicy::ema_period`
+
+    // Ownership. The address is derived from the creator, so the authority
+    // can change without moving the feed.
+    pub creator: Pubkey,
+    pub pending_authority: Pubkey, // Proposed new authority, default if none
+
+    // The fields below fill the space reserved in the original zero-copy
+    // layout, so the account size never changed. Optional features with
+    // more state use their own accounts (`FeedReaders`, `FeedSuccessor`,
+    // `AutoRenew`).
+
+    // Grace period, see `ProtocolConfig::grace_period`
+    pub grace_debt: u64,          // Subscription cost of publishes past the due time, settled on extension
+    pub grace_accrued_until: i64, // Time up to which `grace_debt` has been accrued
+
+    // Shared subscriptions, see `subscribe_to_feed`
+    pub subscribed_until: i64, // Latest due time of a subscriber's `Subscription`, paid by subscribers
 }
 
 impl Feed {
     pub const SEED_PREFIX: &'static [u8] = b"feed";
-    pub const SPACE: usize = 8 + Feed::INIT_SPACE;
+    pub const DEFAULT_ROUND_TIMEOUT: u64 = 3600;
+    pub const SPACE: usize = 8 + size_of::<Feed>();
+
+    // Offsets in the account data, discriminator included
+    pub const LATEST_ANSWER_OFFSET: usize = 8 + offset_of!(Feed, latest_answer);
+    pub const BALANCE_OFFSET: usize = 8 + offset_of!(Feed, balance);
+    pub const SUBSCRIPTION_DUE_TIME_OFFSET: usize = 8 + offset_of!(Feed, subscription_due_time);
+    pub const LATEST_ROUND_ID_OFFSET: usize = 8 + offset_of!(Feed, latest_round_id);
+    pub const MIN_SIGNATURES_THRESHOLD_OFFSET: usize = 8 + offset_of!(Feed, min_signatures_threshold);
+    pub const STATUS_OFFSET: usize = 8 + offset_of!(Feed, status);
+
+    pub fn name(&self) -> &str {
+        from_fixed(&self.name)
+    }
+
+    pub fn set_name(&mut self, name: &str) -> Result<()> {
+        self.name = to_fixed(name)?;
+        Ok(())
+    }
+
+    pub fn ipfs_cid(&self) -> &str {
+        from_fixed(&self.ipfs_cid)
+    }
+
+    pub fn set_ipfs_cid(&mut self, ipfs_cid: &str) -> Result<()> {
+        self.ipfs_cid = to_fixed(ipfs_cid)?;
+        Ok(())
+    }
+
+    pub fn feed_type(&self) -> FeedType {
+        FeedType::from_u8(self.feed_type)
+    }
+
+    pub fn value_type(&self) -> ValueType {
+        ValueType::from_u8(self.value_type)
+    }
+
+    /// Status of the feed. The successor of a deprecated feed is kept in its
+    /// `FeedSuccessor` account and is the default key here.
+    pub fn status(&self) -> FeedStatus {
+        FeedStatus::from_u8(self.status, Pubkey::default())
+    }
+
+    /// Sets the status, recording whether the protocol admin paused the feed.
+    pub fn set_status(&mut self, status: &FeedStatus, by_admin: bool) {
+        self.status = status.to_u8();
+        self.paused_by_admin = (by_admin && *status == FeedStatus::Paused) as u8;
+    }
+
+    pub fn is_paused_by_admin(&self) -> bool {
+        self.paused_by_admin != 0
+    }
+
+    /// Fails with `FeedError::FeedPaused` or `FeedError::FeedRetired` when
+    /// consumers should not read the feed.
+    pub fn check_readable(&self) -> Result<()> {
+        match self.status() {
+            FeedStatus::Paused => err!(FeedError::FeedPaused),
+            FeedStatus::Retired => err!(FeedError::FeedRetired),
+            FeedStatus::Active | FeedStatus::Deprecated { .. } => Ok(()),
+        }
+    }
+
+    pub fn has_restricted_readers(&self) -> bool {
+        self.restricted_readers != 0
+    }
+
+    pub fn latest_answer(&self) -> AnswerV2 {
+        self.latest_answer.into()
+    }
+
+    pub fn update_policy(&self) -> UpdatePolicy {
+        UpdatePolicy {
+            min_update_interval: self.min_update_interval,
+            deviation_threshold_bps: self.deviation_threshold_bps,
+            heartbeat: self.heartbeat,
+            max_deviation_bps: self.max_deviation_bps,
+            outlier_signatures_threshold: self.outlier_signatures_threshold,
+            ema_period: self.ema_period,
+        }
+    }
+
+    pub fn set_update_policy(&mut self, policy: &UpdatePolicy) {
+        self.min_update_interval = policy.min_update_interval;
+        self.deviation_threshold_bps = policy.deviation_threshold_bps;
+        self.heartbeat = policy.heartbeat;
+        self.max_deviation_bps = policy.max_deviation_bps;
+        self.outlier_signatures_threshold = policy.outlier_signatures_threshold;
+        self.ema_period = policy.ema_period;
+    }
+
+    pub fn price_cumulative(&self) -> i128 {
+        i128::from_le_bytes(self.price_cumulative)
+    }
+
+    pub fn ema(&self) -> i128 {
+        i128::from_le_bytes(self.ema)
+    }
+
+    /// Latest answer with the TWAP accumulator, for the history account.
+    pub fn history_entry(&self) -> HistoryEntry {
+        HistoryEntry {
+            answer: self.latest_answer,
+            price_cumulative: self.price_cumulative,
+        }
+    }
+
+    /// Advances the averages of a numeric feed to `answer`. The accumulator
+    /// integrates the latest value over the time elapsed until `answer`; the
+    /// EMA moves towards `answer` by `elapsed / (elapsed + ema_period)`.
+    /// Values without an `i128` view count as zero and leave the EMA unchanged.
+    /// A change of exponent restarts the EMA at `answer`; TWAP windows across
+    /// it are rejected, see `FeedHistory::window_exponent`.
+    fn accumulate(&mut self, answer: &AnswerV2) {
+        let value_type = self.value_type();
+        if !value_type.is_numeric() {
+            return;
+        }
+        let value = value_type.as_i128(&answer.value);
+
+        let Some(elapsed) = self.time_since_latest_answer(answer.timestamp()) else {
+            if let Some(value) = value {
+                self.ema = value.to_le_bytes();
+            }
+            return;
+        };
+        let elapsed = elapsed.max(0) as i128;
+        let rescaled = answer.exponent != self.latest_answer.exponent;
+
+        let latest = value_type.as_i128(&self.latest_answer.value).unwrap_or_default();
+        self.price_cumulative = self
+            .price_cumulative()
+            .wrapping_add(latest.wrapping_mul(elapsed))
+            .to_le_bytes();
+
+        if let Some(value) = value {
+            if rescaled {
+                self.ema = value.to_le_bytes();
+                return;
+            }
+            let ema = self.ema();
+            let step = value.saturating_sub(ema).saturating_mul(elapsed)
+                / (elapsed + self.ema_period as i128).max(1);
+            self.ema = ema.saturating_add(step).to_le_bytes();
+        }
+    }
     
+    /// Time until which the feed is paid for, by its authority
+    /// (`subscription_due_time`) or by subscribers (`subscribed_until`).
+    pub fn covered_until(&self) -> i64 {
+        self.subscription_due_time.max(self.subscribed_until)
+    }
+
     pub fn is_subscription_active(&self, current_time: i64) -> bool {
-        self.subscription_due_time > current_time
+        self.covered_until() > current_time
     }
     
+    /// Whether answers can be published at `current_time`: while the feed is
+    /// paid for and for `grace_period` seconds after it.
+    pub fn accepts_publishes(&self, current_time: i64, grace_period: u64) -> bool {
+        self.covered_until().saturating_add(grace_period as i64) > current_time
+    }
+
+    /// Adds the subscription cost of the time since the feed stopped being
+    /// paid for, or since the last accrual if later, up to `current_time` to
+    /// `grace_debt`.
+    pub fn accrue_grace_debt(&mut self, current_time: i64) {
+        let from = self.covered_until().max(self.grace_accrued_until);
+        if current_time <= from {
+            return;
+        }
+        let cost = (current_time - from) as u128 * self.price_per_second_scaled as u128
+            / ProtocolConfig::SCALAR as u128;
+        self.grace_debt = self
+            .grace_debt
+            .saturating_add(u64::try_from(cost).unwrap_or(u64::MAX));
+        self.grace_accrued_until = current_time;
+    }
+
+    pub fn remaining_priority_fee_budget(&self) -> u64 {
+        self.priority_fee_allowance.saturating_sub(self.consumed_priority_fees)
+    }
+
     pub fn has_priority_fee_budget(&self, required_fee: u64) -> bool {
         self.consumed_priority_fees + required_fee <= self.priority_fee_allowance
     }
     
+    /// Rolling average including a new metered `fee`, over the last `window`
+    /// updates (a cumulative average until `window` samples exist).
+    pub fn smoothed_priority_fee(&self, fee: u64, window: u8) -> u64 {
+        let samples = (self.priority_fee_samples as i128 + 1).min(window.max(1) as i128);
+        let average = self.average_priority_fee as i128;
+        (average + (fee as i128 - average) / samples) as u64
+    }
+
+    pub fn record_priority_fee(&mut self, smoothed_fee: u64) {
+        self.average_priority_fee = smoothed_fee;
+        self.priority_fee_samples = self.priority_fee_samples.saturating_add(1);
+    }
+
+    /// A new round can be opened once the reporting round has finalized,
+    /// or after it timed out without quorum (it is then superseded).
+    /// Rounds time out after `Feed::round_timeout`.
+    pub fn can_start_round(&self, current_time: i64) -> bool {
+        self.reporting_round_id == self.latest_round_id
+            || current_time >= self.reporting_round_started_at.saturating_add(self.round_timeout() as i64)
+    }
+
+    /// One update period (`frequency`), or `DEFAULT_ROUND_TIMEOUT` for feeds
+    /// without one so that rounds can still collect a quorum.
+    pub fn round_timeout(&self) -> u64 {
+        if self.frequency == 0 {
+            Self::DEFAULT_ROUND_TIMEOUT
+        } else {
+            self.frequency
+        }
+    }
+
+    /// Seconds elapsed between the latest answer and `timestamp`, or `None` before the first answer.
+    pub fn time_since_latest_answer(&self, timestamp: i64) -> Option<i64> {
+        if self.latest_answer.round_id == 0 {
+            None
+        } else {
+            Some(timestamp - self.latest_answer.timestamp())
+        }
+    }
+
+    /// Enforces the feed's update policy against a candidate answer.
+    /// The first answer of a feed is always accepted.
+    pub fn check_update_policy(&self, answer: &AnswerV2) -> Result<()> {
+        let Some(elapsed) = self.time_since_latest_answer(answer.timestamp()) else {
+            return Ok(());
+        };
+        let policy = self.update_policy();
+
+        require!(
+            elapsed >= policy.min_update_interval as i64,
+            FeedError::UpdateTooFrequent
+        );
+
+        if !policy.has_trigger_conditions() {
+            return Ok(());
+        }
+
+        let heartbeat_due = policy.heartbeat > 0 && elapsed >= policy.heartbeat as i64;
+        let deviation_met = policy.deviation_threshold_bps > 0
+            && answer.deviation_bps(&self.latest_answer(), self.value_type()) >= policy.deviation_threshold_bps as u64;
+
+        require!(
+            heartbeat_due || deviation_met,
+            FeedError::UpdateConditionsNotMet
+        );
+
+        Ok(())
+    }
+
+    /// Whether `answer` jumps from the latest answer by more than the policy's
+    /// `max_deviation_bps`. Only numeric answers after the first one are guarded.
+    pub fn is_outlier(&self, answer: &AnswerV2) -> bool {
+        let max_deviation_bps = self.max_deviation_bps;
+        max_deviation_bps > 0
+            && self.value_type().is_numeric()
+            && self.latest_answer.round_id != 0
+            && answer.deviation_bps(&self.latest_answer(), self.value_type()) > max_deviation_bps as u64
+    }
+
+    /// Signers required to accept an outlier, never below the feed threshold.
+    pub fn outlier_quorum(&self, node_count: usize) -> usize {
+        let quorum = match self.outlier_signatures_threshold {
+            0 => node_count,
+            threshold => threshold as usize,
+        };
+        quorum.max(self.min_signatures_threshold as usize)
+    }
+
+    /// Maximum submissions a round may collect: the outlier quorum when the
+    /// guard is enabled, so that rounds can reach it, the feed threshold otherwise.
+    pub fn max_round_submissions(&self, node_count: usize) -> u8 {
+        if self.max_deviation_bps > 0 {
+            self.outlier_quorum(node_count).min(u8::MAX as usize) as u8
+        } else {
+            self.min_signatures_threshold
+        }
+    }
+
+    /// Latest answer for consumers. Fails if the feed is paused or retired,
+    /// the subscription has expired, the answer is older than `max_age_seconds`
+    /// or was signed by fewer than `min_signers` nodes.
+    pub fn read_latest_answer(
+        &self,
+        current_time: i64,
+        max_age_seconds: u64,
+        min_signers: u8,
+    ) -> Result<AnswerV2> {
+        self.check_readable()?;
+        require!(
+            self.is_subscription_active(current_time),
+            FeedError::SubscriptionExpired
+        );
+
+        let age = self
+            .time_since_latest_answer(current_time)
+            .ok_or(error!(FeedError::NoAnswer))?;
+        require!(
+            age <= i64::try_from(max_age_seconds).unwrap_or(i64::MAX),
+            FeedError::StaleAnswer
+        );
+        require!(
+            self.latest_answer.signer_count >= min_signers,
+            FeedError::InsufficientSigners
+        );
+
+        Ok(self.latest_answer())
+    }
+
+    /// Latest answer decoded according to the feed's value type.
+    pub fn latest_typed_value(&self) -> Result<TypedValue> {
+        self.value_type().decode(&self.latest_answer())
+    }
+
+    /// Round ID assigned to the next answer. Any reporting round still open is superseded.
+    pub fn next_round_id(&self) -> u64 {
+        self.reporting_round_id + 1
+    }
+
+    /// Sets the latest answer and updates the averages. See `record_history`
+    /// for the history account.
+    pub fn push_answer(&mut self, answer: AnswerV2) {
+        self.accumulate(&answer);
+        self.latest_answer = answer.into();
+        self.latest_round_id = answer.round_id;
+        self.reporting_round_id = self.reporting_round_id.max(answer.round_id);
+    }
+
+    pub fn has_history(&self) -> bool {
+        self.history != Pubkey::default()
+    }
+    
     pub fn remaining_subscription_time(&self, current_time: i64) -> i64 {
         if self.subscription_due_time > current_time {
             self.subscription_due_time - current_time
@@ -51,4 +427,151 @@ impl Feed {
             0
         }
     }
+
+    /// Cost of `duration` seconds at the feed's price.
+    pub fn subscription_cost(&self, duration: u64) -> Result<u64> {
+        let cost = (self.price_per_second_scaled as u128)
+            .checked_mul(duration as u128)
+            .ok_or(FeedError::ArithmeticOverflow)?
+            / ProtocolConfig::SCALAR as u128;
+        Ok(u64::try_from(cost).map_err(|_| FeedError::ArithmeticOverflow)?)
+    }
+
+    /// Extends the subscription by `duration` seconds, from the due time or from
+    /// `current_time` if it has passed, and adds `priority_budget` to the
+    /// allowance. Returns the cost, which includes the settled `grace_debt` and
+    /// is added to the balance.
+    pub fn extend_subscription(&mut self, current_time: i64, duration: u64, priority_budget: u64) -> Result<u64> {
+        let total_cost = self
+            .subscription_cost(duration)?
+            .checked_add(priority_budget)
+            .and_then(|cost| cost.checked_add(self.grace_debt))
+            .ok_or(FeedError::ArithmeticOverflow)?;
+        let due_time = i64::try_from(duration)
+            .ok()
+            .and_then(|duration| self.subscription_due_time.max(current_time).checked_add(duration))
+            .ok_or(FeedError::ArithmeticOverflow)?;
+
+        self.grace_debt = 0;
+        self.subscription_due_time = due_time;
+        self.priority_fee_allowance = self
+            .priority_fee_allowance
+            .checked_add(priority_budget)
+            .ok_or(FeedError::ArithmeticOverflow)?;
+        self.balance = self.balance.checked_add(total_cost).ok_or(FeedError::ArithmeticOverflow)?;
+
+        Ok(total_cost)
+    }
+
+    /// Records a subscriber's access until `due_time`, funded by the
+    /// subscriber's `cost`. The feed stays live at least as long through
+    /// `subscribed_until`; the authority's `subscription_due_time` is not
+    /// extended.
+    pub fn add_subscriber_time(&mut self, due_time: i64, cost: u64) -> Result<()> {
+        self.balance = self.balance.checked_add(cost).ok_or(FeedError::ArithmeticOverflow)?;
+        self.subscribed_until = self.subscribed_until.max(due_time);
+        Ok(())
+    }
+
+    /// Whether a subscriber's `Subscription` is still running at `current_time`.
+    pub fn has_active_subscribers(&self, current_time: i64) -> bool {
+        self.subscribed_until > current_time
+    }
+
+    /// Prepaid amount not used by `current_time`: the authority's remaining
+    /// subscription time at the feed's price plus the unspent priority fee
+    /// allowance, less the grace debt still owed, bounded by the feed balance.
+    /// Time paid by subscribers is never included.
+    pub fn refundable_amount(&self, current_time: i64) -> u64 {
+        let subscription = self.remaining_subscription_time(current_time) as u128
+            * self.price_per_second_scaled as u128
+            / ProtocolConfig::SCALAR as u128;
+        let priority_fees = self.priority_fee_allowance.saturating_sub(self.consumed_priority_fees);
+        u64::try_from(subscription)
+            .unwrap_or(u64::MAX)
+            .saturating_add(priority_fees)
+            .saturating_sub(self.grace_debt)
+            .min(self.balance)
+    }
+}
+
+/// Feed layout before `AnswerV2`, kept to migrate existing accounts.
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct LegacyFeed {
+    #[max_len(64)]
+    pub name: String,
+    pub authority: Pubkey,
+    pub feed_type: FeedType,
+    pub job_id: [u8; 32],
+    pub data_source: Pubkey,
+    pub balance: u64,
+    pub min_signatures_threshold: u8,
+    pub frequency: u64,
+    #[max_len(60)]
+    pub ipfs_cid: String,
+    pub latest_answer: Answer,
+    #[max_len(MAX_HISTORY)]
+    pub answer_history: Vec<Answer>,
+    pub history_idx: u64,
+    pub subscription_due_time: i64,
+    pub price_per_second_scaled: u64,
+    pub priority_fee_allowance: u64,
+    pub consumed_priority_fees: u64,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl LegacyFeed {
+    pub const SPACE: usize = 8 + LegacyFeed::INIT_SPACE;
+
+    /// Converts the legacy feed. Legacy values are untyped 32-byte words and
+    /// are kept as `U256`. History entries are assigned round IDs in
+    /// chronological order and returned oldest first, for a `FeedHistory`.
+    pub fn into_feed(self) -> Result<(Feed, Vec<HistoryEntry>)> {
+        let mut history = self.answer_history;
+        history.sort_by_key(|answer| answer.timestamp);
+
+        let mut feed = Feed::zeroed();
+        feed.set_name(&self.name)?;
+        feed.set_ipfs_cid(&self.ipfs_cid)?;
+        feed.authority = self.authority;
+        feed.creator = self.authority;
+        feed.feed_type = self.feed_type.to_u8();
+        feed.value_type = ValueType::U256.to_u8();
+        feed.job_id = self.job_id;
+        feed.data_source = self.data_source;
+        feed.balance = self.balance;
+        feed.min_signatures_threshold = self.min_signatures_threshold;
+        feed.frequency = self.frequency;
+        feed.subscription_due_time = self.subscription_due_time;
+        feed.price_per_second_scaled = self.price_per_second_scaled;
+        feed.priority_fee_allowance = self.priority_fee_allowance;
+        feed.consumed_priority_fees = self.consumed_priority_fees;
+        feed.created_at = self.created_at;
+        feed.bump = self.bump;
+
+        let history = history
+            .iter()
+            .enumerate()
+            .map(|(i, answer)| {
+                feed.push_answer(AnswerV2::from_legacy(answer, i as u64 + 1));
+                feed.history_entry()
+            })
+            .collect();
+
+        Ok((feed, history))
+    }
+}
+
+/// Zero-padded copy of `value`, which must fit in `N` bytes.
+fn to_fixed<const N: usize>(value: &str) -> Result<[u8; N]> {
+    require!(value.len() <= N, FeedError::InvalidFeedConfig);
+    let mut fixed = [0u8; N];
+    fixed[..value.len()].copy_from_slice(value.as_bytes());
+    Ok(fixed)
+}
+
+fn from_fixed(value: &[u8]) -> &str {
+    let len = value.iter().position(|&byte| byte == 0).unwrap_or(value.len());
+    std::str::from_utf8(&value[..len]).unwrap_or_default()
 }
@@ -0,0 +1,100 @@
+use super::{encode_i128, AnswerV2, MAX_NAME_LEN};
+use crate::error::{BundleError, FeedError};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+pub const MAX_BUNDLE_SLOTS: usize = 64;
+pub const MAX_SLOT_NAME_LEN: usize = 16;
+
+/// Values of a bundle signed as one report. Values are compact `i64` words in
+/// the bundle's exponent so that a report for every slot fits in a
+/// transaction.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct BundleReport {
+    pub values: Vec<i64>, // One per slot, in slot order
+    pub observed_from: i64,
+    pub observed_to: i64,
+}
+
+impl BundleReport {
+    /// Message the nodes sign: `sha256(bundle || report)`, with the report in
+    /// its borsh encoding. Signing the hash keeps the Ed25519 instructions
+    /// small however many slots the bundle has.
+    pub fn signing_message(&self, bundle: &Pubkey) -> [u8; 32] {
+        hashv(&[bundle.as_ref(), &borsh::to_vec(self).unwrap()]).to_bytes()
+    }
+}
+
+/// Named values updated together under one signed report (`publish_bundle`).
+/// Slots are added by the authority up to the capacity chosen at creation,
+/// and read by index (`read_bundle_slot`).
+#[account]
+#[derive(Default)]
+pub struct FeedBundle {
+    pub authority: Pubkey,
+    pub name: String,
+    pub min_signatures_threshold: u8,
+    pub exponent: i32, // Shared by all slots
+    pub capacity: u16,
+    pub round_id: u64, // Last published report, 0 before the first
+    pub observed_from: i64,
+    pub observed_to: i64,
+    pub signer_count: u8,
+    pub reported_slots: u16, // Slots covered by the latest report; later slots have no value yet
+    pub slot_names: Vec<String>,
+    pub values: Vec<i64>,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl FeedBundle {
+    pub const SEED_PREFIX: &'static [u8] = b"bundle";
+
+    /// Account size for `capacity` slots with names of up to `MAX_SLOT_NAME_LEN` bytes.
+    pub fn space(capacity: u16) -> usize {
+        8 + 32
+            + (4 + MAX_NAME_LEN)
+            + 1
+            + 4
+            + 2
+            + 8 * 3
+            + 1
+            + 2
+            + 4 + capacity as usize * (4 + MAX_SLOT_NAME_LEN)
+            + 4 + capacity as usize * 8
+            + 8
+            + 1
+    }
+
+    /// Slot `index` as an answer of the bundle's latest report. Fails with
+    /// `BundleError::SlotNotReported` for a slot added after that report.
+    pub fn slot_answer(
+        &self,
+        index: u16,
+        current_time: i64,
+        max_age_seconds: u64,
+        min_signers: u8,
+    ) -> Result<AnswerV2> {
+        let value = *self
+            .values
+            .get(index as usize)
+            .ok_or(error!(BundleError::SlotNotFound))?;
+        require!(self.round_id != 0, FeedError::NoAnswer);
+        require!(index < self.reported_slots, BundleError::SlotNotReported);
+        require!(
+            current_time - self.observed_to <= i64::try_from(max_age_seconds).unwrap_or(i64::MAX),
+            FeedError::StaleAnswer
+        );
+        require!(self.signer_count >= min_signers, FeedError::InsufficientSigners);
+
+        Ok(AnswerV2 {
+            round_id: self.round_id,
+            value: encode_i128(value as i128),
+            exponent: self.exponent,
+            confidence: 0,
+            observed_from: self.observed_from,
+            observed_to: self.observed_to,
+            signer_count: self.signer_count,
+        })
+    }
+}
@@ -0,0 +1,231 @@
+use super::{AnswerRecord, AnswerV2, Feed, ValueType};
+use crate::error::FeedError;
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use std::mem::size_of;
+
+/// Zero-copy ring buffer of a feed's answers. The header is followed by
+/// `capacity` `HistoryEntry` entries; `head` is the next slot to write.
+/// Use `FeedHistory::load` / `load_mut` to access the entries.
+#[account(zero_copy)]
+#[derive(Default)]
+pub struct FeedHistory {
+    pub feed: Pubkey,
+    pub capacity: u32,
+    pub len: u32,
+    pub head: u32,
+    pub bump: u8,
+    pub _padding: [u8; 3],
+}
+
+impl FeedHistory {
+    pub const SEED_PREFIX: &'static [u8] = b"history";
+    pub const HEADER_SPACE: usize = 8 + size_of::<FeedHistory>();
+    pub const MAX_CAPACITY: u32 = 4096;
+
+    pub fn space(capacity: u32) -> usize {
+        Self::HEADER_SPACE + capacity as usize * size_of::<HistoryEntry>()
+    }
+
+    /// Splits the account data into the header and the entries.
+    pub fn load(data: &[u8]) -> Result<(&FeedHistory, &[HistoryEntry])> {
+        require!(
+            data.len() >= Self::HEADER_SPACE && data.starts_with(FeedHistory::DISCRIMINATOR),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        let (header, entries) = data[8..].split_at(size_of::<FeedHistory>());
+        let header: &FeedHistory = bytemuck::from_bytes(header);
+        let entries: &[HistoryEntry] =
+            bytemuck::cast_slice(&entries[..header.capacity as usize * size_of::<HistoryEntry>()]);
+        Ok((header, entries))
+    }
+
+    pub fn load_mut(data: &mut [u8]) -> Result<(&mut FeedHistory, &mut [HistoryEntry])> {
+        require!(
+            data.len() >= Self::HEADER_SPACE && data.starts_with(FeedHistory::DISCRIMINATOR),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        Ok(Self::load_init_mut(data))
+    }
+
+    /// Like `load_mut`, for an account initialized by the current instruction
+    /// (Anchor writes its discriminator on exit).
+    pub fn load_init_mut(data: &mut [u8]) -> (&mut FeedHistory, &mut [HistoryEntry]) {
+        let (header, entries) = data[8..].split_at_mut(size_of::<FeedHistory>());
+        let header: &mut FeedHistory = bytemuck::from_bytes_mut(header);
+        let entries: &mut [HistoryEntry] = bytemuck::cast_slice_mut(
+            &mut entries[..header.capacity as usize * size_of::<HistoryEntry>()],
+        );
+        (header, entries)
+    }
+
+    /// Writes an entry, overwriting the oldest one once full.
+    pub fn push(&mut self, entries: &mut [HistoryEntry], entry: HistoryEntry) {
+        if self.capacity == 0 {
+            return;
+        }
+        entries[self.head as usize] = entry;
+        self.head = (self.head + 1) % self.capacity;
+        self.len = (self.len + 1).min(self.capacity);
+    }
+
+    /// Entry `index` in chronological order, 0 being the oldest.
+    pub fn get(&self, entries: &[HistoryEntry], index: u32) -> Option<AnswerV2> {
+        if index >= self.len {
+            return None;
+        }
+        Some(self.record(entries, index).answer.into())
+    }
+
+    fn record<'a>(&self, entries: &'a [HistoryEntry], index: u32) -> &'a HistoryEntry {
+        let oldest = (self.head + self.capacity - self.len) % self.capacity;
+        &entries[((oldest + index) % self.capacity) as usize]
+    }
+
+    /// Chronological index of the first entry for which `pred` is false.
+    /// Entries are ordered by both timestamp and round ID.
+    fn partition_point(&self, entries: &[HistoryEntry], pred: impl Fn(&HistoryEntry) -> bool) -> u32 {
+        let (mut low, mut high) = (0, self.len);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if pred(self.record(entries, mid)) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+
+    /// Answer in effect at `timestamp`: the latest one observed at or before it.
+    /// `None` if `timestamp` predates the recorded history.
+    pub fn answer_at(&self, entries: &[HistoryEntry], timestamp: i64) -> Option<HistoricalAnswer> {
+        let index = self.partition_point(entries, |entry| entry.answer.timestamp() <= timestamp);
+        let answer = self.get(entries, index.checked_sub(1)?)?;
+        Some(HistoricalAnswer { answer, age: timestamp - answer.timestamp() })
+    }
+
+    /// Answer of round `round_id`, aged at `now`. `None` if the round is not
+    /// recorded (dropped from the buffer, superseded or rejected).
+    pub fn round(&self, entries: &[HistoryEntry], round_id: u64, now: i64) -> Option<HistoricalAnswer> {
+        let index = self.partition_point(entries, |entry| entry.answer.round_id < round_id);
+        let answer = self.get(entries, index).filter(|answer| answer.round_id == round_id)?;
+        Some(HistoricalAnswer { answer, age: now - answer.timestamp() })
+    }
+
+    /// TWAP accumulator at `timestamp`, extended from the entry in effect.
+    /// `None` if `timestamp` predates the recorded history.
+    pub fn cumulative_at(
+        &self,
+        entries: &[HistoryEntry],
+        value_type: ValueType,
+        timestamp: i64,
+    ) -> Option<i128> {
+        let index = self.partition_point(entries, |entry| entry.answer.timestamp() <= timestamp);
+        let entry = self.record(entries, index.checked_sub(1)?);
+        let elapsed = (timestamp - entry.answer.timestamp()) as i128;
+        Some(entry.price_cumulative().wrapping_add(
+            value_type.as_i128(&entry.answer.value).unwrap_or_default().wrapping_mul(elapsed),
+        ))
+    }
+
+    /// Time-weighted average value between `from` and `to`, in the answers'
+    /// scale. `None` unless the history covers the window.
+    pub fn twap(
+        &self,
+        entries: &[HistoryEntry],
+        value_type: ValueType,
+        from: i64,
+        to: i64,
+    ) -> Option<i128> {
+        if from >= to {
+            return None;
+        }
+        let delta = self
+            .cumulative_at(entries, value_type, to)?
+            .wrapping_sub(self.cumulative_at(entries, value_type, from)?);
+        Some(delta / (to - from) as i128)
+    }
+
+    /// Exponent shared by the answers in effect between `from` and `to`.
+    /// `None` if it changed within the window, whose TWAP would then mix
+    /// scales, or if the history does not cover the window.
+    pub fn window_exponent(&self, entries: &[HistoryEntry], from: i64, to: i64) -> Option<i32> {
+        let first = self
+            .partition_point(entries, |entry| entry.answer.timestamp() <= from)
+            .checked_sub(1)?;
+        let end = self.partition_point(entries, |entry| entry.answer.timestamp() <= to);
+        let exponent = self.record(entries, first).answer.exponent;
+        (first..end)
+            .all(|index| self.record(entries, index).answer.exponent == exponent)
+            .then_some(exponent)
+    }
+
+    pub fn latest(&self, entries: &[HistoryEntry]) -> Option<AnswerV2> {
+        self.len.checked_sub(1).and_then(|index| self.get(entries, index))
+    }
+
+    /// All entries in chronological order.
+    pub fn chronological<'a>(
+        &'a self,
+        entries: &'a [HistoryEntry],
+    ) -> impl Iterator<Item = AnswerV2> + 'a {
+        (0..self.len).filter_map(move |index| self.get(entries, index))
+    }
+
+    /// Grows the buffer to `new_capacity` once the account has been resized.
+    /// Entries are rewritten oldest first so that the ring continues after them.
+    pub fn grow(&mut self, entries: &mut [HistoryEntry], new_capacity: u32) {
+        let ordered: Vec<HistoryEntry> = (0..self.len).map(|index| *self.record(entries, index)).collect();
+        entries[..ordered.len()].copy_from_slice(&ordered);
+        self.capacity = new_capacity;
+        self.head = self.len;
+    }
+}
+
+/// An answer from the history with its age in seconds at the time of the lookup.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct HistoricalAnswer {
+    pub answer: AnswerV2,
+    pub age: i64,
+}
+
+/// Time-weighted and exponential moving averages of a numeric feed, in the
+/// scale of `exponent`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct AverageAnswer {
+    pub twap: i128,
+    pub ema: i128,
+    pub exponent: i32,
+    pub window_start: i64,
+    pub window_end: i64,
+}
+
+/// Answer stored in a `FeedHistory`, with the feed's TWAP accumulator at its timestamp.
+#[zero_copy]
+#[derive(Default)]
+pub struct HistoryEntry {
+    pub answer: AnswerRecord,
+    pub price_cumulative: [u8; 16], // i128, little-endian
+}
+
+impl HistoryEntry {
+    pub fn price_cumulative(&self) -> i128 {
+        i128::from_le_bytes(self.price_cumulative)
+    }
+}
+
+/// Appends the latest answer of the feed to its history account, if the feed
+/// has one. The account passed must then be the one recorded in `Feed.history`.
+pub fn record_history(feed: &Feed, history: Option<&AccountInfo>) -> Result<()> {
+    if !feed.has_history() {
+        return Ok(());
+    }
+    let history = history.ok_or(error!(FeedError::InvalidHistoryAccount))?;
+    require_keys_eq!(history.key(), feed.history, FeedError::InvalidHistoryAccount);
+
+    let mut data = history.try_borrow_mut_data()?;
+    let (header, entries) = FeedHistory::load_mut(&mut data)?;
+    header.push(entries, feed.history_entry());
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use crate::error::FeedError;
+use crate::utils::calling_program;
+use anchor_lang::prelude::*;
+
+pub const MAX_FEED_READERS: usize = 16;
+
+/// Consumer programs allowed to read a personal feed (`set_feed_readers`).
+/// While the list is not empty, the feed is only readable through
+/// `read_answer_gated`, called by one of these programs.
+#[account]
+#[derive(Default, InitSpace)]
+pub struct FeedReaders {
+    pub feed: Pubkey,
+    #[max_len(MAX_FEED_READERS)]
+    pub readers: Vec<Pubkey>, // Program IDs
+    pub bump: u8,
+}
+
+impl FeedReaders {
+    pub const SEED_PREFIX: &'static [u8] = b"readers";
+    pub const SPACE: usize = 8 + FeedReaders::INIT_SPACE;
+
+    pub fn is_allowed(&self, program_id: &Pubkey) -> bool {
+        self.readers.contains(program_id)
+    }
+
+    /// Fails with `FeedError::ReaderNotAllowed` unless the calling program,
+    /// found through the instructions sysvar, is in the list.
+    pub fn check_caller(&self, instructions_sysvar: &AccountInfo) -> Result<()> {
+        let caller = calling_program(instructions_sysvar)?;
+        require!(self.is_allowed(&caller), FeedError::ReaderNotAllowed);
+        Ok(())
+    }
+}
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+/// Replacement of a deprecated feed, for consumers to move to. Set by
+/// `set_feed_status` when the feed is deprecated and kept by later
+/// transitions; it only applies while the feed is deprecated or retired.
+#[account]
+#[derive(Default, InitSpace)]
+pub struct FeedSuccessor {
+    pub feed: Pubkey,
+    pub successor: Pubkey,
+    pub bump: u8,
+}
+
+impl FeedSuccessor {
+    pub const SEED_PREFIX: &'static [u8] = b"successor";
+    pub const SPACE: usize = 8 + FeedSuccessor::INIT_SPACE;
+}
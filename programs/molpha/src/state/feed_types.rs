@@ -11,7 +11,99 @@ impl FeedType {
     pub fn to_u8(&self) -> u8 {
         *self as u8
     }
+    pub fn from_u8(value: u8) -> Self {
+        if value == Self::Personal as u8 {
+            Self::Personal
+        } else {
+            Self::Public
+        }
+    }
     pub fn to_seed(&self) -> [u8; 1] {
         [self.to_u8()]
     }
 }
+
+/// Lifecycle of a feed. Answers are accepted until the feed is retired;
+/// consumer reads fail while it is paused or retired. A deprecated feed keeps
+/// working and points consumers to its successor.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub enum FeedStatus {
+    #[default]
+    Active,
+    Paused,
+    Deprecated { successor: Pubkey },
+    Retired,
+}
+
+impl FeedStatus {
+    pub fn to_u8(&self) -> u8 {
+        match self {
+            Self::Active => 0,
+            Self::Paused => 1,
+            Self::Deprecated { .. } => 2,
+            Self::Retired => 3,
+        }
+    }
+
+    pub fn from_u8(value: u8, successor: Pubkey) -> Self {
+        match value {
+            1 => Self::Paused,
+            2 => Self::Deprecated { successor },
+            3 => Self::Retired,
+            _ => Self::Active,
+        }
+    }
+
+    pub fn successor(&self) -> Option<Pubkey> {
+        match self {
+            Self::Deprecated { successor } => Some(*successor),
+            _ => None,
+        }
+    }
+
+    pub fn accepts_answers(&self) -> bool {
+        *self != Self::Retired
+    }
+
+    /// Whether the feed authority, or the protocol admin when `is_admin`, can
+    /// move the feed from this status to `next`. Owners pause and resume,
+    /// deprecate (again, to change the successor) and retire deprecated
+    /// feeds. The admin can also pause or retire any feed. Retired is final.
+    pub fn can_transition_to(&self, next: &FeedStatus, is_admin: bool) -> bool {
+        use FeedStatus::*;
+        match (self, next) {
+            (Retired, _) => false,
+            (_, Paused | Retired) if is_admin => true,
+            (Active, Paused) | (Paused, Active) => true,
+            (_, Deprecated { .. }) => true,
+            (Deprecated { .. }, Retired) => true,
+            _ => false,
+        }
+    }
+}
+
+/// On-chain update policy for a feed. A zero value disables the respective condition.
+/// An answer must respect `min_update_interval` and, when a deviation threshold or
+/// heartbeat is configured, either deviate enough from the latest answer or be due
+/// by heartbeat.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub struct UpdatePolicy {
+    pub min_update_interval: u64,     // Minimum seconds between answers
+    pub deviation_threshold_bps: u16, // Deviation from the latest answer that triggers an update
+    pub heartbeat: u64,               // Seconds after which an update is due regardless of deviation
+    pub max_deviation_bps: u16,       // Larger jumps need `outlier_signatures_threshold` signers (0 disables)
+    pub outlier_signatures_threshold: u8, // Elevated quorum for outliers, 0 means all registered nodes
+    pub ema_period: u64,              // Time constant of the EMA in seconds, 0 tracks the latest answer
+}
+
+impl UpdatePolicy {
+    pub fn is_valid(&self) -> bool {
+        (self.heartbeat == 0 || self.heartbeat >= self.min_update_interval)
+            && (self.max_deviation_bps == 0
+                || self.max_deviation_bps >= self.deviation_threshold_bps)
+    }
+
+    pub fn has_trigger_conditions(&self) -> bool {
+        self.deviation_threshold_bps > 0 || self.heartbeat > 0
+    }
+}
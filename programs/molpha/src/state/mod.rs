@@ -1,15 +1,35 @@
 pub mod answer;
+pub mod auto_renew;
 pub mod node_registry;
 pub mod feed;
+pub mod feed_bundle;
+pub mod feed_history;
+pub mod feed_readers;
+pub mod feed_successor;
 pub mod feed_types;
 pub mod protocol_config;
 pub mod data_source;
+pub mod derived_feed;
 pub mod node;
+pub mod price_update;
+pub mod round;
+pub mod subscription;
+pub mod value_type;
 
 pub use answer::*;
+pub use auto_renew::*;
 pub use data_source::*;
+pub use derived_feed::*;
 pub use feed::*;
+pub use feed_bundle::*;
+pub use feed_history::*;
+pub use feed_readers::*;
+pub use feed_successor::*;
 pub use feed_types::*;
 pub use node::*;
 pub use node_registry::*;
+pub use price_update::*;
 pub use protocol_config::*;
+pub use round::*;
+pub use subscription::*;
+pub use value_type::*;
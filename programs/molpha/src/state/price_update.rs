@@ -0,0 +1,19 @@
+use super::AnswerV2;
+use anchor_lang::prelude::*;
+
+/// Answer verified in pull mode (`verify_report`), stored for a consumer.
+/// One account per (feed, consumer); only the consumer can write to it.
+#[account]
+#[derive(Default, InitSpace)]
+pub struct PriceUpdate {
+    pub feed: Pubkey,
+    pub consumer: Pubkey,  // Write authority, usually a PDA of the consumer program
+    pub answer: AnswerV2,  // Round ID is 0, pulled reports are not part of feed rounds
+    pub posted_at: i64,
+    pub bump: u8,
+}
+
+impl PriceUpdate {
+    pub const SEED_PREFIX: &'static [u8] = b"price_update";
+    pub const SPACE: usize = 8 + PriceUpdate::INIT_SPACE;
+}
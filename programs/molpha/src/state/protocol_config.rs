@@ -1,5 +1,9 @@
 use anchor_lang::prelude::*;
 
+// Size of the protocol config before the transmitter schedule was added. New
+// fields are inserted before `bump`, see `migrate_protocol_config`.
+pub const PROTOCOL_CONFIG_V1_SPACE: usize = 116;
+
 #[account]
 #[derive(Default, InitSpace)]
 pub struct ProtocolConfig {
@@ -16,6 +20,17 @@ pub struct ProtocolConfig {
     pub priority_fee_buffer_percentage: u16, // Buffer for priority fees (e.g., 150 = 50% buffer)
     pub max_priority_fee_coverage: u64,      // Maximum priority fee to cover per transaction
     pub priority_fee_smoothing_window: u8,   // Number of recent transactions to average
+
+    // Transmitter rotation, see `ProtocolConfig::designated_transmitter`
+    pub transmitter_window: u64,             // Seconds per transmitter turn (0 disables the schedule)
+    pub transmitter_fallback_delay: u64,     // Seconds into a turn after which anyone can transmit
+
+    pub crank_tip_bps: u16,                  // Share of an auto-renewal paid to the cranker (basis points)
+
+    // Expiring subscriptions, see `Feed::accepts_publishes`
+    pub grace_period: u64,                   // Seconds past the due time during which publishes continue, accruing debt
+    pub low_subscription_threshold: u64,     // Remaining seconds below which `SubscriptionLow` is emitted (0 disables)
+    pub low_priority_budget_threshold: u64,  // Remaining priority fee budget below which `SubscriptionLow` is emitted (0 disables)
     pub bump: u8,
 }
 
@@ -23,4 +38,47 @@ impl ProtocolConfig {
     pub const SEED_PREFIX: &'static [u8] = b"config";
     pub const SPACE: usize = 8 + Self::INIT_SPACE;
     pub const SCALAR: u64 = 1_000_000; // Same as EVM version
+
+    /// Decodes a config written with fewer fields (`data` without the
+    /// discriminator). Fields missing from the older layout read as zero.
+    pub fn from_previous_layout(data: &[u8]) -> Result<Self> {
+        let (bump, fields) = data.split_last().ok_or(ProgramError::InvalidAccountData)?;
+        let mut padded = fields.to_vec();
+        padded.resize(Self::INIT_SPACE - 1, 0);
+        padded.push(*bump);
+        Ok(Self::deserialize(&mut padded.as_slice())?)
+    }
+
+    pub fn has_transmitter_schedule(&self) -> bool {
+        self.transmitter_window > 0
+    }
+
+    /// Committee member scheduled to transmit `round_id` of `feed` at `current_time`.
+    /// Turns of `transmitter_window` seconds rotate through the committee, starting
+    /// from an offset derived from the feed address and the round ID.
+    pub fn designated_transmitter(
+        &self,
+        nodes: &[Pubkey],
+        feed: &Pubkey,
+        round_id: u64,
+        current_time: i64,
+    ) -> Option<Pubkey> {
+        if !self.has_transmitter_schedule() || nodes.is_empty() {
+            return None;
+        }
+
+        let feed_offset = u64::from_le_bytes(feed.to_bytes()[..8].try_into().unwrap());
+        let turn = current_time.max(0) as u64 / self.transmitter_window;
+        let index = feed_offset
+            .wrapping_add(round_id)
+            .wrapping_add(turn)
+            % nodes.len() as u64;
+        Some(nodes[index as usize])
+    }
+
+    /// Whether the current turn is past its exclusive part, letting anyone transmit.
+    pub fn is_transmitter_fallback_open(&self, current_time: i64) -> bool {
+        !self.has_transmitter_schedule()
+            || current_time.max(0) as u64 % self.transmitter_window >= self.transmitter_fallback_delay
+    }
 }
@@ -0,0 +1,88 @@
+use super::{AnswerReport, AnswerV2, ValueType};
+use anchor_lang::prelude::*;
+
+/// A single node observation submitted towards a round.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct RoundSubmission {
+    pub node: Pubkey,
+    pub report: AnswerReport,
+}
+
+/// Multi-transaction reporting round (FluxAggregator-style).
+/// Each node submits its observation in its own transaction; the round
+/// finalizes into `Feed.latest_answer` once `threshold` submissions exist,
+/// or once `outlier_quorum` nodes have submitted if the answer is an outlier.
+/// Both are fixed when the round opens, so registry changes cannot raise them
+/// beyond the submissions the account has room for.
+#[account]
+#[derive(Default, InitSpace)]
+pub struct Round {
+    pub feed: Pubkey,
+    pub round_id: u64,
+    pub opened_by: Pubkey,   // Pays the rent, refunded on close
+    pub started_at: i64,
+    pub threshold: u8,       // Snapshot of the feed threshold when the round opened
+    pub outlier_quorum: u8,  // Snapshot of the outlier quorum when the round opened, at most `max_submissions`
+    pub max_submissions: u8, // Submissions the account has room for, see `Round::space`
+    pub finalized: bool,
+    pub answer: AnswerV2,
+    #[max_len(0)]
+    pub submissions: Vec<RoundSubmission>, // Sized per round, see `Round::space`
+    pub bump: u8,
+}
+
+impl Round {
+    pub const SEED_PREFIX: &'static [u8] = b"round";
+
+    /// Account space for a round collecting `threshold` submissions.
+    pub fn space(threshold: u8) -> usize {
+        8 + Self::INIT_SPACE + threshold as usize * RoundSubmission::INIT_SPACE
+    }
+
+    pub fn is_timed_out(&self, current_time: i64, timeout: u64) -> bool {
+        current_time >= self.started_at.saturating_add(timeout as i64)
+    }
+
+    pub fn has_submitted(&self, node: &Pubkey) -> bool {
+        self.submissions.iter().any(|s| s.node == *node)
+    }
+
+    pub fn has_quorum(&self) -> bool {
+        self.submissions.len() >= self.threshold as usize
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.submissions.len() >= self.max_submissions as usize
+    }
+
+    /// Exponent shared by all submissions, set by the first one.
+    pub fn exponent(&self) -> Option<i32> {
+        self.submissions.first().map(|s| s.report.exponent)
+    }
+
+    /// Value shared by all submissions for non-numeric value types, set by the first one.
+    pub fn value(&self) -> Option<[u8; 32]> {
+        self.submissions.first().map(|s| s.report.value)
+    }
+
+    /// Aggregates the submissions into an answer: median value and confidence
+    /// (upper median for an even count) over the union of the observation windows.
+    /// Non-numeric values are identical across submissions, see `Round::value`.
+    pub fn aggregate(&self, value_type: ValueType) -> AnswerV2 {
+        let mut values: Vec<[u8; 32]> = self.submissions.iter().map(|s| s.report.value).collect();
+        let mut confidences: Vec<u128> = self.submissions.iter().map(|s| s.report.confidence).collect();
+        values.sort_by(|a, b| value_type.compare(a, b));
+        confidences.sort_unstable();
+
+        let mid = self.submissions.len() / 2;
+        AnswerV2 {
+            round_id: self.round_id,
+            value: values[mid],
+            exponent: self.exponent().unwrap_or_default(),
+            confidence: confidences[mid],
+            observed_from: self.submissions.iter().map(|s| s.report.observed_from).min().unwrap_or_default(),
+            observed_to: self.submissions.iter().map(|s| s.report.observed_to).max().unwrap_or_default(),
+            signer_count: self.submissions.len() as u8,
+        }
+    }
+}
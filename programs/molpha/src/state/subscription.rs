@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+/// A consumer's paid access to a public feed (`subscribe_to_feed`). One
+/// account per (feed, subscriber); payments go to the feed's pooled balance.
+#[account]
+#[derive(Default, InitSpace)]
+pub struct Subscription {
+    pub feed: Pubkey,
+    pub subscriber: Pubkey, // Wallet or consumer program PDA, signs reads
+    pub due_time: i64,
+    pub total_paid: u64,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl Subscription {
+    pub const SEED_PREFIX: &'static [u8] = b"subscription";
+    pub const SPACE: usize = 8 + Subscription::INIT_SPACE;
+
+    pub fn is_active(&self, current_time: i64) -> bool {
+        self.due_time > current_time
+    }
+}
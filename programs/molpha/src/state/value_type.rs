@@ -0,0 +1,190 @@
+use super::AnswerV2;
+use crate::error::FeedError;
+use anchor_lang::prelude::*;
+use std::cmp::Ordering;
+
+/// Schema of a feed's answer values. Values are 32-byte words encoded like
+/// EVM ABI words: big-endian, signed types in two's complement, strings
+/// left-aligned and zero-padded.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub enum ValueType {
+    #[default]
+    I128Price,   // Signed price scaled by the answer exponent
+    U256,        // Unsigned 256-bit integer
+    Bool,        // 0 or 1
+    Bytes32Hash, // Opaque 32-byte hash
+    ShortString, // Up to 32 printable ASCII characters
+    Timestamp,   // Non-negative unix timestamp in seconds
+}
+
+/// A decoded answer value.
+#[derive(Clone, PartialEq, Eq)]
+pub enum TypedValue {
+    I128Price { value: i128, exponent: i32, confidence: u128 },
+    U256([u8; 32]),
+    Bool(bool),
+    Bytes32Hash([u8; 32]),
+    ShortString(String),
+    Timestamp(i64),
+}
+
+impl ValueType {
+    pub fn to_u8(&self) -> u8 {
+        *self as u8
+    }
+
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::U256,
+            2 => Self::Bool,
+            3 => Self::Bytes32Hash,
+            4 => Self::ShortString,
+            5 => Self::Timestamp,
+            _ => Self::I128Price,
+        }
+    }
+
+    /// Whether answers of this type can be compared numerically
+    /// (deviation checks and median aggregation).
+    pub fn is_numeric(&self) -> bool {
+        !matches!(self, Self::Bytes32Hash | Self::ShortString)
+    }
+
+    /// Numeric view of a value, if the type is numeric and the value fits in an `i128`.
+    pub fn as_i128(&self, value: &[u8; 32]) -> Option<i128> {
+        match self {
+            Self::I128Price | Self::Bool | Self::Timestamp => decode_signed(value),
+            Self::U256 => decode_unsigned(value),
+            Self::Bytes32Hash | Self::ShortString => None,
+        }
+    }
+
+    /// Orders two values of this type: `U256` values as unsigned 256-bit
+    /// integers, other numeric types as signed integers.
+    pub fn compare(&self, a: &[u8; 32], b: &[u8; 32]) -> Ordering {
+        match self {
+            Self::U256 | Self::Bytes32Hash | Self::ShortString => a.cmp(b),
+            Self::I128Price | Self::Bool | Self::Timestamp => decode_signed(a).cmp(&decode_signed(b)),
+        }
+    }
+
+    /// Checks a value against the schema. Only price and integer types carry
+    /// an exponent and confidence interval.
+    pub fn is_valid(&self, value: &[u8; 32], exponent: i32, confidence: u128) -> bool {
+        let scaled = matches!(self, Self::I128Price | Self::U256);
+        if !scaled && (exponent != 0 || confidence != 0) {
+            return false;
+        }
+
+        match self {
+            Self::I128Price => decode_signed(value).is_some(),
+            Self::U256 | Self::Bytes32Hash => true,
+            Self::Bool => matches!(decode_signed(value), Some(0 | 1)),
+            Self::ShortString => decode_short_string(value).is_some(),
+            Self::Timestamp => decode_signed(value).is_some_and(|t| (0..=i64::MAX as i128).contains(&t)),
+        }
+    }
+
+    pub fn decode(&self, answer: &AnswerV2) -> Result<TypedValue> {
+        let value = &answer.value;
+        let decoded = match self {
+            Self::I128Price => decode_signed(value).map(|v| TypedValue::I128Price {
+                value: v,
+                exponent: answer.exponent,
+                confidence: answer.confidence,
+            }),
+            Self::U256 => Some(TypedValue::U256(*value)),
+            Self::Bool => match decode_signed(value) {
+                Some(0) => Some(TypedValue::Bool(false)),
+                Some(1) => Some(TypedValue::Bool(true)),
+                _ => None,
+            },
+            Self::Bytes32Hash => Some(TypedValue::Bytes32Hash(*value)),
+            Self::ShortString => decode_short_string(value).map(TypedValue::ShortString),
+            Self::Timestamp => decode_signed(value)
+                .and_then(|t| i64::try_from(t).ok())
+                .map(TypedValue::Timestamp),
+        };
+
+        decoded.ok_or(error!(FeedError::InvalidValue))
+    }
+}
+
+/// Encodes a signed integer as a big-endian two's complement word.
+pub fn encode_i128(value: i128) -> [u8; 32] {
+    let mut word = if value < 0 { [0xff; 32] } else { [0; 32] };
+    word[16..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+fn decode_signed(word: &[u8; 32]) -> Option<i128> {
+    let low = i128::from_be_bytes(word[16..].try_into().unwrap());
+    let extension = if low < 0 { 0xff } else { 0 };
+    word[..16].iter().all(|&b| b == extension).then_some(low)
+}
+
+fn decode_unsigned(word: &[u8; 32]) -> Option<i128> {
+    let low = i128::from_be_bytes(word[16..].try_into().unwrap());
+    (low >= 0 && word[..16].iter().all(|&b| b == 0)).then_some(low)
+}
+
+/// Relative change from `previous` to `current` in basis points, for unsigned
+/// 256-bit values. Both are shifted down to 112 significant bits, which keeps
+/// the result exact enough whenever it fits in a `u64`.
+pub(crate) fn unsigned_deviation_bps(current: &[u8; 32], previous: &[u8; 32]) -> u64 {
+    let (current, previous) = (split_u256(current), split_u256(previous));
+    if previous == (0, 0) {
+        return u64::MAX;
+    }
+    let diff = if current >= previous {
+        sub_u256(current, previous)
+    } else {
+        sub_u256(previous, current)
+    };
+
+    let shift = bits_u256(diff).max(bits_u256(previous)).saturating_sub(112);
+    let (diff, previous) = (shr_u256(diff, shift), shr_u256(previous, shift));
+    if previous == 0 {
+        return u64::MAX;
+    }
+    u64::try_from(diff * 10_000 / previous).unwrap_or(u64::MAX)
+}
+
+// 256-bit words as (high, low) halves, ordered like the numbers they encode
+fn split_u256(word: &[u8; 32]) -> (u128, u128) {
+    (
+        u128::from_be_bytes(word[..16].try_into().unwrap()),
+        u128::from_be_bytes(word[16..].try_into().unwrap()),
+    )
+}
+
+fn sub_u256(a: (u128, u128), b: (u128, u128)) -> (u128, u128) {
+    let (low, borrow) = a.1.overflowing_sub(b.1);
+    (a.0 - b.0 - borrow as u128, low)
+}
+
+fn bits_u256(value: (u128, u128)) -> u32 {
+    if value.0 > 0 {
+        256 - value.0.leading_zeros()
+    } else {
+        128 - value.1.leading_zeros()
+    }
+}
+
+// Low half of `value >> shift`, for shifts that leave at most 112 bits
+fn shr_u256(value: (u128, u128), shift: u32) -> u128 {
+    match shift {
+        0 => value.1,
+        1..=127 => (value.0 << (128 - shift)) | (value.1 >> shift),
+        _ => value.0 >> (shift - 128),
+    }
+}
+
+fn decode_short_string(word: &[u8; 32]) -> Option<String> {
+    let len = word.iter().position(|&b| b == 0).unwrap_or(word.len());
+    let (text, padding) = word.split_at(len);
+    if !text.iter().all(|b| (0x20..=0x7e).contains(b)) || padding.iter().any(|&b| b != 0) {
+        return None;
+    }
+    String::from_utf8(text.to_vec()).ok()
+}
@@ -1,7 +1,11 @@
-use crate::error::NodeRegistryError;
+use crate::error::{FeedError, NodeRegistryError};
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program;
+use anchor_lang::solana_program::instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT};
+use anchor_lang::solana_program::{ed25519_program, sysvar};
 
+pub mod merkle;
+pub mod metering;
 pub mod pricing;
 
 /// Parses a legacy Ed25519 verification instruction to extract the signer's public key and the message.
@@ -45,3 +49,71 @@ pub fn parse_ed25519_instruction(
         message_bytes.to_vec(),
     ))
 }
+
+/// Closes `account` into `destination` if it is a live Molpha account,
+/// returning its rent. Used for per-feed PDAs that may not exist.
+pub fn close_if_initialized<'info>(
+    account: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+) -> Result<()> {
+    if account.owner != &crate::ID || account.data_is_empty() {
+        return Ok(());
+    }
+    let lamports = account.lamports();
+    **destination.try_borrow_mut_lamports()? = destination
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(FeedError::ArithmeticOverflow)?;
+    **account.try_borrow_mut_lamports()? = 0;
+    account.assign(&solana_program::system_program::ID);
+    account.resize(0)?;
+    Ok(())
+}
+
+/// Program of the top-level instruction being executed: the program that
+/// called into Molpha through CPI, or Molpha itself for a direct call. Fails
+/// with `FeedError::ReaderNotAllowed` when Molpha is called from a nested
+/// CPI, where the top-level program is not the caller.
+pub fn calling_program(instructions_sysvar: &AccountInfo) -> Result<Pubkey> {
+    require!(
+        get_stack_height() <= TRANSACTION_LEVEL_STACK_HEIGHT + 1,
+        FeedError::ReaderNotAllowed
+    );
+    let current_instruction_index =
+        sysvar::instructions::load_current_index_checked(instructions_sysvar)?;
+    let instruction = sysvar::instructions::load_instruction_at_checked(
+        current_instruction_index as usize,
+        instructions_sysvar,
+    )?;
+    Ok(instruction.program_id)
+}
+
+/// Collects the distinct registered nodes that signed `message` in the Ed25519
+/// instructions preceding the current instruction.
+pub fn collect_node_signers(
+    instructions_sysvar: &AccountInfo,
+    message: &[u8],
+    nodes: &[Pubkey],
+) -> Result<Vec<Pubkey>> {
+    let current_instruction_index =
+        sysvar::instructions::load_current_index_checked(instructions_sysvar)?;
+
+    let mut unique_valid_signers = Vec::new();
+    for i in (0..current_instruction_index).rev() {
+        let instruction =
+            sysvar::instructions::load_instruction_at_checked(i as usize, instructions_sysvar)?;
+
+        if instruction.program_id == ed25519_program::ID {
+            if let Ok((signer_pubkey, signed_message)) = parse_ed25519_instruction(&instruction) {
+                if signed_message == message
+                    && nodes.contains(&signer_pubkey)
+                    && !unique_valid_signers.contains(&signer_pubkey)
+                {
+                    unique_valid_signers.push(signer_pubkey);
+                }
+            }
+        }
+    }
+
+    Ok(unique_valid_signers)
+}
@@ -0,0 +1,31 @@
+use crate::state::AnswerReport;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+// Domain separation between leaves and inner nodes
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+/// Leaf committing a report to a feed: `sha256(0x00 || feed || report)`,
+/// where the report is its borsh encoding.
+pub fn leaf_hash(feed: &Pubkey, report: &AnswerReport) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, feed.as_ref(), &borsh::to_vec(report).unwrap()]).to_bytes()
+}
+
+/// Inner node over a sorted pair: `sha256(0x01 || min(a, b) || max(a, b))`.
+/// Sorting the pair means proofs don't need to carry left/right positions.
+pub fn node_hash(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, left, right]).to_bytes()
+}
+
+/// Root of the tree containing `leaf`, given its sibling path.
+/// With an empty proof the leaf is its own root.
+pub fn compute_root(leaf: [u8; 32], proof: &[[u8; 32]]) -> [u8; 32] {
+    proof.iter().fold(leaf, |hash, sibling| node_hash(&hash, sibling))
+}
+
+/// Checks that `leaf` is part of the tree with `root`, given its sibling path.
+pub fn verify_proof(leaf: [u8; 32], proof: &[[u8; 32]], root: &[u8; 32]) -> bool {
+    compute_root(leaf, proof) == *root
+}
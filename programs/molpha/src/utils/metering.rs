@@ -0,0 +1,143 @@
+use super::pricing::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{ed25519_program, pubkey, sysvar};
+
+pub const COMPUTE_BUDGET_PROGRAM_ID: Pubkey = pubkey!("ComputeBudget111111111111111111111111111111");
+
+pub const DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT: u32 = 200_000;
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+pub const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+const MICRO_LAMPORTS_PER_LAMPORT: u128 = 1_000_000;
+
+/// ComputeBudget program instructions, in the order of their borsh tags.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ComputeBudgetInstruction {
+    RequestUnitsDeprecated { units: u32, additional_fee: u32 },
+    RequestHeapFrame(u32),
+    SetComputeUnitLimit(u32),
+    SetComputeUnitPrice(u64), // Micro-lamports per compute unit
+    SetLoadedAccountsDataSizeLimit(u32),
+}
+
+impl ComputeBudgetInstruction {
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let (&tag, rest) = data
+            .split_first()
+            .ok_or(error!(ErrorCode::InvalidInstruction))?;
+
+        let instruction = match tag {
+            0 => Self::RequestUnitsDeprecated {
+                units: read_u32(rest, 0)?,
+                additional_fee: read_u32(rest, 4)?,
+            },
+            1 => Self::RequestHeapFrame(read_u32(rest, 0)?),
+            2 => Self::SetComputeUnitLimit(read_u32(rest, 0)?),
+            3 => Self::SetComputeUnitPrice(read_u64(rest, 0)?),
+            4 => Self::SetLoadedAccountsDataSizeLimit(read_u32(rest, 0)?),
+            _ => return err!(ErrorCode::InvalidInstruction),
+        };
+
+        Ok(instruction)
+    }
+}
+
+/// Fees paid by the current transaction, metered from its instructions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TransactionFees {
+    pub compute_unit_limit: u32,
+    pub compute_unit_price: u64,  // Micro-lamports per compute unit
+    pub additional_fee: u64,      // Lamports from the deprecated `RequestUnits`
+    pub ed25519_signatures: u64,
+}
+
+impl TransactionFees {
+    /// Reads every instruction of the transaction from the instructions sysvar.
+    /// Without `SetComputeUnitLimit` the runtime default applies: 200k compute
+    /// units per instruction, capped at 1.4M like any requested limit.
+    pub fn from_instructions(instructions_sysvar: &AccountInfo) -> Result<Self> {
+        let instruction_count = {
+            let data = instructions_sysvar.try_borrow_data()?;
+            u16::from_le_bytes(read_array(&data, 0)?) as usize
+        };
+
+        let mut fees = Self::default();
+        let mut requested_limit = None;
+        let mut other_instructions = 0u32;
+
+        for i in 0..instruction_count {
+            let instruction =
+                sysvar::instructions::load_instruction_at_checked(i, instructions_sysvar)?;
+
+            if instruction.program_id == COMPUTE_BUDGET_PROGRAM_ID {
+                match ComputeBudgetInstruction::parse(&instruction.data)? {
+                    ComputeBudgetInstruction::RequestUnitsDeprecated { units, additional_fee } => {
+                        requested_limit = Some(units);
+                        fees.additional_fee = additional_fee as u64;
+                    }
+                    ComputeBudgetInstruction::SetComputeUnitLimit(units) => {
+                        requested_limit = Some(units);
+                    }
+                    ComputeBudgetInstruction::SetComputeUnitPrice(price) => {
+                        fees.compute_unit_price = price;
+                    }
+                    ComputeBudgetInstruction::RequestHeapFrame(_)
+                    | ComputeBudgetInstruction::SetLoadedAccountsDataSizeLimit(_) => {}
+                }
+                continue;
+            }
+
+            other_instructions += 1;
+            if instruction.program_id == ed25519_program::ID {
+                // First byte of an Ed25519 instruction is its signature count
+                fees.ed25519_signatures += *instruction.data.first().unwrap_or(&0) as u64;
+            }
+        }
+
+        fees.compute_unit_limit = requested_limit
+            .unwrap_or(other_instructions.saturating_mul(DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT))
+            .min(MAX_COMPUTE_UNIT_LIMIT);
+
+        Ok(fees)
+    }
+
+    /// Priority fee in lamports for the requested compute unit limit, rounded up.
+    pub fn priority_fee(&self) -> u64 {
+        let micro_lamports = self.compute_unit_price as u128 * self.compute_unit_limit as u128;
+        let lamports = micro_lamports.div_ceil(MICRO_LAMPORTS_PER_LAMPORT);
+        u64::try_from(lamports)
+            .unwrap_or(u64::MAX)
+            .saturating_add(self.additional_fee)
+    }
+
+    /// Base fee of the transaction itself, signed by the transmitter.
+    pub fn base_fee(&self) -> u64 {
+        LAMPORTS_PER_SIGNATURE
+    }
+
+    /// Base fees of the signatures verified by Ed25519 instructions.
+    pub fn signature_fee(&self) -> u64 {
+        self.ed25519_signatures.saturating_mul(LAMPORTS_PER_SIGNATURE)
+    }
+
+    /// Fees covered by the protocol, capped at `max_coverage` per transaction.
+    pub fn covered_fee(&self, max_coverage: u64) -> u64 {
+        self.priority_fee()
+            .saturating_add(self.base_fee())
+            .saturating_add(self.signature_fee())
+            .min(max_coverage)
+    }
+}
+
+fn read_array<const N: usize>(data: &[u8], offset: usize) -> Result<[u8; N]> {
+    data.get(offset..offset + N)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(error!(ErrorCode::InvalidInstruction))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32> {
+    read_array(data, offset).map(u32::from_le_bytes)
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Result<u64> {
+    read_array(data, offset).map(u64::from_le_bytes)
+}
@@ -44,38 +44,12 @@ pub fn calculate_price_per_second_scaled(
     Ok(buffered_price)
 }
 
-pub fn calculate_priority_fee_from_instructions(
-    instructions_sysvar: &UncheckedAccount,
-    estimated_compute_units: u32,
-) -> Result<u64> {
-    let instructions_account = instructions_sysvar.to_account_info();
-    let current_index = anchor_lang::solana_program::sysvar::instructions::load_current_index_checked(&instructions_account)?;
-    
-    for i in 0..current_index {
-        let instruction = anchor_lang::solana_program::sysvar::instructions::load_instruction_at_checked(
-            i as usize, 
-            &instructions_account
-        )?;
-        
-        // Check for ComputeBudgetProgram ID (ComputeBudgetProgram11111111111111111111111111)
-        let compute_budget_program_id = anchor_lang::solana_program::pubkey!("ComputeBudget111111111111111111111111111111");
-        if instruction.program_id == compute_budget_program_id {
-            if let Ok(microlamports_per_cu) = parse_compute_unit_price(&instruction.data) {
-                let priority_fee = (microlamports_per_cu as u64 * estimated_compute_units as u64) / 1_000_000;
-                return Ok(priority_fee);
-            }
-        }
-    }
-    
-    Ok(0) // No priority fee instruction found
-}
-
-pub fn estimate_compute_units(node_count: u32, history_length: u32) -> u32 {
-    let base_units = 5000;
-    let signature_units = node_count * 1000;
-    let history_units = if (history_length as usize) < crate::state::feed::MAX_HISTORY { 500 } else { 200 };
-    
-    base_units + signature_units + history_units
+/// Fee for verifying one report in pull mode: the subscription price of one update period.
+pub fn calculate_pull_fee(feed: &Feed) -> Result<u64> {
+    feed.price_per_second_scaled
+        .checked_mul(feed.frequency)
+        .and_then(|x| x.checked_div(ProtocolConfig::SCALAR))
+        .ok_or(error!(ErrorCode::ArithmeticError))
 }
 
 // Simplified power function for pricing calculations
@@ -98,19 +72,6 @@ fn precise_pow(base: u64, numerator: u64, denominator: u64, scalar: u64) -> Resu
     }
 }
 
-fn parse_compute_unit_price(data: &[u8]) -> Result<u32> {
-    // Parse SetComputeUnitPrice instruction
-    if data.len() >= 9 && data[0] == 3 { // SetComputeUnitPrice discriminator
-        let microlamports = u64::from_le_bytes([
-            data[1], data[2], data[3], data[4],
-            data[5], data[6], data[7], data[8],
-        ]);
-        Ok(microlamports as u32)
-    } else {
-        Err(error!(ErrorCode::InvalidInstruction))
-    }
-}
-
 #[error_code]
 pub enum ErrorCode {
     #[msg("Arithmetic error occurred")]
@@ -0,0 +1,277 @@
+import { assert } from "chai";
+import { ComputeBudgetProgram, Keypair, PublicKey, Transaction } from "@solana/web3.js";
+import { BN } from "@coral-xyz/anchor";
+import {
+  TOKEN_PROGRAM_ID,
+  createApproveCheckedInstruction,
+  getAccount,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import {
+  setupTestContext,
+  initializeProtocol,
+  TestContext,
+  createFeedParams,
+  createTestFeed,
+} from "../setup";
+import { Clock } from "solana-bankrun";
+
+describe("Auto-Renew", () => {
+  let ctx: TestContext;
+  let feedPDA: PublicKey;
+  let autoRenewPDA: PublicKey;
+  const DAY = 86400;
+
+  function getAutoRenewPda(feed: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("auto_renew"), feed.toBuffer()],
+      ctx.molphaProgram.programId
+    )[0];
+  }
+
+  function setAutoRenew(
+    duration: number,
+    priorityBudget: number,
+    window: number,
+    feed = feedPDA,
+    authority: Keypair = ctx.authority.payer
+  ) {
+    return ctx.molphaProgram.methods
+      .setAutoRenew(new BN(duration), new BN(priorityBudget), new BN(window))
+      .accountsPartial({
+        feed,
+        autoRenew: getAutoRenewPda(feed),
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  // The provider wallet cranks, with its own token account receiving the tip.
+  // `computeUnits` tells apart otherwise identical cranks sent in one slot.
+  function crankRenew(
+    feed = feedPDA,
+    computeUnits = 200_000,
+    ownerTokenAccount = ctx.userTokenAccount
+  ) {
+    return ctx.molphaProgram.methods
+      .crankRenew()
+      .accountsPartial({
+        feed,
+        autoRenew: getAutoRenewPda(feed),
+        cranker: ctx.authority.publicKey,
+        ownerTokenAccount,
+        crankerTokenAccount: ctx.userTokenAccount,
+        programTokenAccount: ctx.programTokenAccount,
+        protocolConfig: ctx.protocolConfigPDA,
+        underlyingToken: ctx.underlyingTokenMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: computeUnits })])
+      .rpc();
+  }
+
+  async function approveProtocol(amount: number) {
+    const approve = new Transaction().add(
+      createApproveCheckedInstruction(
+        ctx.userTokenAccount,
+        ctx.underlyingTokenMint,
+        ctx.protocolConfigPDA,
+        ctx.authority.publicKey,
+        amount,
+        6
+      )
+    );
+    await ctx.provider.sendAndConfirm(approve, [ctx.authority.payer]);
+  }
+
+  async function expectError(promise: Promise<unknown>, code: string) {
+    try {
+      await promise;
+      assert.fail(`Should have failed with ${code}`);
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes(code) || error.message.includes("custom program error"),
+        error.message
+      );
+    }
+  }
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeProtocol(ctx);
+    await ctx.molphaProgram.methods
+      .updateProtocolConfig({
+        basePricePerSecondScaled: new BN(0),
+        frequencyCoefficient: new BN(0),
+        signersCoefficient: new BN(0),
+        rewardPercentage: new BN(0),
+        priorityFeeBufferPercentage: 0,
+        maxPriorityFeeCoverage: new BN(0),
+        priorityFeeSmoothingWindow: 0,
+        transmitterWindow: new BN(0),
+        transmitterFallbackDelay: new BN(0),
+        crankTipBps: 500,
+        gracePeriod: new BN(0),
+        lowSubscriptionThreshold: new BN(0),
+        lowPriorityBudgetThreshold: new BN(0),
+      })
+      .accountsPartial({
+        protocolConfig: ctx.protocolConfigPDA,
+        authority: ctx.authority.publicKey,
+      })
+      .rpc();
+
+    feedPDA = await createTestFeed(ctx, createFeedParams("auto-renew-test", { public: {} }), DAY);
+    autoRenewPDA = getAutoRenewPda(feedPDA);
+  });
+
+  it("Fails for feeds that never opted in", async () => {
+    await expectError(crankRenew(), "AccountNotInitialized");
+  });
+
+  it("Fails when auto-renew is disabled", async () => {
+    await setAutoRenew(0, 0, 0);
+    const settings = await ctx.molphaProgram.account.autoRenew.fetch(autoRenewPDA);
+    assert.ok(settings.feed.equals(feedPDA));
+    await expectError(crankRenew(), "AutoRenewDisabled");
+  });
+
+  it("Fails before the renewal window", async () => {
+    await setAutoRenew(DAY, 10_000, 0);
+    await expectError(crankRenew(), "RenewalNotDue");
+  });
+
+  it("Renews within the window and tips the cranker", async () => {
+    await setAutoRenew(2 * DAY, 10_000, 2 * DAY - 1);
+    await approveProtocol(100_000);
+
+    const feedBefore = await ctx.molphaProgram.account.feed.fetch(feedPDA);
+    const programBefore = await getAccount(ctx.provider.connection, ctx.programTokenAccount);
+    await crankRenew();
+    const feedAfter = await ctx.molphaProgram.account.feed.fetch(feedPDA);
+    const programAfter = await getAccount(ctx.provider.connection, ctx.programTokenAccount);
+    const owner = await getAccount(ctx.provider.connection, ctx.userTokenAccount);
+
+    assert.equal(
+      feedAfter.subscriptionDueTime.sub(feedBefore.subscriptionDueTime).toNumber(),
+      2 * DAY
+    );
+    assert.equal(
+      feedAfter.priorityFeeAllowance.sub(feedBefore.priorityFeeAllowance).toNumber(),
+      10_000
+    );
+    assert.equal((programAfter.amount - programBefore.amount).toString(), "10000");
+    // Cost plus a 5% tip drawn from the allowance
+    assert.equal(owner.delegatedAmount.toString(), (100_000 - 10_500).toString());
+  });
+
+  it("Fails once the approved amount is spent", async () => {
+    await approveProtocol(1_000);
+    await expectError(crankRenew(), "insufficient funds");
+  });
+
+  it("Rejects a renewal period under a day", async () => {
+    await expectError(setAutoRenew(3600, 0, DAY), "MinimumExtensionTime");
+  });
+
+  it("Rejects a window as long as the renewal period", async () => {
+    await expectError(setAutoRenew(DAY, 0, DAY), "InvalidFeedConfig");
+    await expectError(setAutoRenew(DAY, 0, DAY + 1), "InvalidFeedConfig");
+    await expectError(setAutoRenew(DAY, 0, 2 ** 53 - 1), "InvalidFeedConfig");
+  });
+
+  it("Renews an expired feed once for back-to-back cranks", async () => {
+    const expiredFeed = await createTestFeed(
+      ctx,
+      createFeedParams("auto-renew-expired", { public: {} }),
+      DAY
+    );
+    const feed = await ctx.molphaProgram.account.feed.fetch(expiredFeed);
+    const clock = await ctx.provider.context.banksClient.getClock();
+    const now = feed.subscriptionDueTime.toNumber() + DAY;
+    ctx.provider.context.setClock(
+      new Clock(
+        clock.slot,
+        clock.epochStartTimestamp,
+        clock.epoch,
+        clock.leaderScheduleEpoch,
+        BigInt(now)
+      )
+    );
+
+    await setAutoRenew(DAY, 0, DAY - 1, expiredFeed);
+    await approveProtocol(100_000);
+    await crankRenew(expiredFeed);
+    const renewed = await ctx.molphaProgram.account.feed.fetch(expiredFeed);
+    assert.equal(renewed.subscriptionDueTime.toNumber(), now + DAY);
+
+    await expectError(crankRenew(expiredFeed, 200_001), "RenewalNotDue");
+    const after = await ctx.molphaProgram.account.feed.fetch(expiredFeed);
+    assert.equal(after.subscriptionDueTime.toNumber(), now + DAY);
+  });
+
+  it("Stops renewing after an authority transfer until the new authority opts in", async () => {
+    const transferredFeed = await createTestFeed(
+      ctx,
+      createFeedParams("auto-renew-transfer", { public: {} }),
+      DAY
+    );
+    await setAutoRenew(2 * DAY, 0, 2 * DAY - 1, transferredFeed);
+
+    const newOwner = Keypair.generate();
+    await ctx.molphaProgram.methods
+      .proposeFeedAuthority(newOwner.publicKey)
+      .accountsPartial({ feed: transferredFeed, authority: ctx.authority.publicKey })
+      .rpc();
+    await ctx.molphaProgram.methods
+      .acceptFeedAuthority()
+      .accountsPartial({ feed: transferredFeed, newAuthority: newOwner.publicKey })
+      .signers([newOwner])
+      .rpc();
+
+    // The new authority's token account, delegated to the protocol config
+    const newOwnerTokenAccount = getAssociatedTokenAddressSync(
+      ctx.underlyingTokenMint,
+      newOwner.publicKey
+    );
+    const data = Buffer.alloc(165);
+    ctx.underlyingTokenMint.toBuffer().copy(data, 0); // mint
+    newOwner.publicKey.toBuffer().copy(data, 32); // owner
+    data.writeBigUInt64LE(BigInt(1_000_000), 64); // amount
+    data.writeUInt8(1, 108); // state (1 = initialized)
+    ctx.provider.context.setAccount(newOwnerTokenAccount, {
+      lamports: 2039280,
+      executable: false,
+      owner: TOKEN_PROGRAM_ID,
+      data,
+    });
+    const approve = new Transaction().add(
+      createApproveCheckedInstruction(
+        newOwnerTokenAccount,
+        ctx.underlyingTokenMint,
+        ctx.protocolConfigPDA,
+        newOwner.publicKey,
+        100_000,
+        6
+      )
+    );
+    await ctx.provider.sendAndConfirm(approve, [ctx.authority.payer, newOwner]);
+
+    // The settings were configured by the previous authority
+    await expectError(crankRenew(transferredFeed, 200_000, ctx.userTokenAccount), "Unauthorized");
+    await expectError(crankRenew(transferredFeed, 200_000, newOwnerTokenAccount), "Unauthorized");
+
+    await setAutoRenew(2 * DAY, 0, 2 * DAY - 1, transferredFeed, newOwner);
+    const settings = await ctx.molphaProgram.account.autoRenew.fetch(getAutoRenewPda(transferredFeed));
+    assert.ok(settings.payer.equals(newOwner.publicKey));
+
+    const before = await ctx.molphaProgram.account.feed.fetch(transferredFeed);
+    await crankRenew(transferredFeed, 200_001, newOwnerTokenAccount);
+    const after = await ctx.molphaProgram.account.feed.fetch(transferredFeed);
+    assert.equal(
+      after.subscriptionDueTime.sub(before.subscriptionDueTime).toNumber(),
+      2 * DAY
+    );
+  });
+});
@@ -0,0 +1,269 @@
+import { assert } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { BN } from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAccount } from "@solana/spl-token";
+import {
+  setupTestContext,
+  initializeProtocol,
+  TestContext,
+  createFeedParams,
+  createTestFeed,
+  getFeedHistoryPda,
+  getFeedAccountPda,
+} from "../setup";
+import { Clock } from "solana-bankrun";
+
+describe("Close Feed Instruction", () => {
+  let ctx: TestContext;
+
+  function feedAccountPda(seed: string, feed: PublicKey) {
+    return getFeedAccountPda(ctx.molphaProgram.programId, seed, feed)[0];
+  }
+
+  function closeFeed(feed: PublicKey, history: PublicKey | null) {
+    return ctx.molphaProgram.methods
+      .closeFeed()
+      .accountsPartial({
+        feed,
+        history,
+        readers: feedAccountPda("readers", feed),
+        successor: feedAccountPda("successor", feed),
+        autoRenew: feedAccountPda("auto_renew", feed),
+        authority: ctx.authority.publicKey,
+        userTokenAccount: ctx.userTokenAccount,
+        programTokenAccount: ctx.programTokenAccount,
+        protocolConfig: ctx.protocolConfigPDA,
+        underlyingToken: ctx.underlyingTokenMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      });
+  }
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeProtocol(ctx);
+  });
+
+  it("Refunds the unused subscription and priority fee allowance", async () => {
+    const feedPDA = await createTestFeed(
+      ctx,
+      createFeedParams("close-feed-test", { public: {} }),
+      86400,
+      1000
+    );
+    const feed = await ctx.molphaProgram.account.feed.fetch(feedPDA);
+    const clock = await ctx.provider.context.banksClient.getClock();
+    const remaining = feed.subscriptionDueTime.sub(new BN(Number(clock.unixTimestamp)));
+    const expectedRefund = BN.min(
+      remaining
+        .mul(feed.pricePerSecondScaled)
+        .div(new BN(1000000))
+        .add(feed.priorityFeeAllowance.sub(feed.consumedPriorityFees)),
+      feed.balance
+    );
+
+    const before = await getAccount(ctx.provider.connection, ctx.userTokenAccount);
+    await closeFeed(feedPDA, null).rpc();
+    const after = await getAccount(ctx.provider.connection, ctx.userTokenAccount);
+
+    assert.equal((after.amount - before.amount).toString(), expectedRefund.toString());
+    assert.isNull(await ctx.provider.connection.getAccountInfo(feedPDA));
+  });
+
+  it("Closes the feed history together with the feed", async () => {
+    const feedPDA = await createTestFeed(
+      ctx,
+      createFeedParams("close-feed-history", { public: {} })
+    );
+    const [historyPDA] = getFeedHistoryPda(ctx.molphaProgram.programId, feedPDA);
+    await ctx.molphaProgram.methods
+      .createFeedHistory(5)
+      .accountsPartial({
+        feed: feedPDA,
+        history: historyPDA,
+        authority: ctx.authority.publicKey,
+      })
+      .rpc();
+
+    try {
+      await closeFeed(feedPDA, null).rpc();
+      assert.fail("Should have failed with InvalidHistoryAccount");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("InvalidHistoryAccount") ||
+          error.message.includes("custom program error")
+      );
+    }
+
+    await closeFeed(feedPDA, historyPDA).rpc();
+    assert.isNull(await ctx.provider.connection.getAccountInfo(feedPDA));
+    assert.isNull(await ctx.provider.connection.getAccountInfo(historyPDA));
+  });
+
+  it("Closes the reader list and auto-renew settings with the feed", async () => {
+    const feedPDA = await createTestFeed(
+      ctx,
+      createFeedParams("close-feed-settings", { personal: {} })
+    );
+    const readers = feedAccountPda("readers", feedPDA);
+    const autoRenew = feedAccountPda("auto_renew", feedPDA);
+    await ctx.molphaProgram.methods
+      .setFeedReaders([Keypair.generate().publicKey])
+      .accountsPartial({ feed: feedPDA, readers, authority: ctx.authority.publicKey })
+      .rpc();
+    await ctx.molphaProgram.methods
+      .setAutoRenew(new BN(86400), new BN(0), new BN(3600))
+      .accountsPartial({ feed: feedPDA, autoRenew, authority: ctx.authority.publicKey })
+      .rpc();
+
+    await closeFeed(feedPDA, null).rpc();
+    assert.isNull(await ctx.provider.connection.getAccountInfo(feedPDA));
+    assert.isNull(await ctx.provider.connection.getAccountInfo(readers));
+    assert.isNull(await ctx.provider.connection.getAccountInfo(autoRenew));
+    assert.isNull(
+      await ctx.provider.connection.getAccountInfo(feedAccountPda("successor", feedPDA))
+    );
+  });
+
+  it("Rejects the history of another feed", async () => {
+    const ownerPDA = await createTestFeed(
+      ctx,
+      createFeedParams("close-feed-owner", { public: {} })
+    );
+    const [historyPDA] = getFeedHistoryPda(ctx.molphaProgram.programId, ownerPDA);
+    await ctx.molphaProgram.methods
+      .createFeedHistory(5)
+      .accountsPartial({
+        feed: ownerPDA,
+        history: historyPDA,
+        authority: ctx.authority.publicKey,
+      })
+      .rpc();
+    const feedPDA = await createTestFeed(
+      ctx,
+      createFeedParams("close-feed-no-history", { public: {} })
+    );
+
+    try {
+      await closeFeed(feedPDA, historyPDA).rpc();
+      assert.fail("Should have failed with InvalidHistoryAccount");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("InvalidHistoryAccount") ||
+          error.message.includes("custom program error")
+      );
+    }
+    assert.isNotNull(await ctx.provider.connection.getAccountInfo(historyPDA));
+  });
+
+  it("Fails when called by someone other than the authority", async () => {
+    const feedPDA = await createTestFeed(
+      ctx,
+      createFeedParams("close-feed-unauth", { public: {} })
+    );
+    const other = Keypair.generate();
+
+    try {
+      await closeFeed(feedPDA, null)
+        .accountsPartial({ authority: other.publicKey })
+        .signers([other])
+        .rpc();
+      assert.fail("Should have failed with has_one constraint");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("ConstraintHasOne") ||
+          error.message.includes("custom program error") ||
+          error.message.includes("Error")
+      );
+    }
+    assert.isNotNull(await ctx.provider.connection.getAccountInfo(feedPDA));
+  });
+
+  function subscribe(feed: PublicKey, durationSeconds: number) {
+    const [subscription] = PublicKey.findProgramAddressSync(
+      [Buffer.from("subscription"), feed.toBuffer(), ctx.authority.publicKey.toBuffer()],
+      ctx.molphaProgram.programId
+    );
+    return ctx.molphaProgram.methods
+      .subscribeToFeed(new BN(durationSeconds))
+      .accountsPartial({
+        feed,
+        subscription,
+        subscriber: ctx.authority.publicKey,
+        subscriberTokenAccount: ctx.userTokenAccount,
+        programTokenAccount: ctx.programTokenAccount,
+        protocolConfig: ctx.protocolConfigPDA,
+        underlyingToken: ctx.underlyingTokenMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+  }
+
+  async function setTime(unixTimestamp: number) {
+    const clock = await ctx.provider.context.banksClient.getClock();
+    ctx.provider.context.setClock(
+      new Clock(
+        clock.slot,
+        clock.epochStartTimestamp,
+        clock.epoch,
+        clock.leaderScheduleEpoch,
+        BigInt(unixTimestamp)
+      )
+    );
+  }
+
+  it("Keeps subscriber payments out of the refund", async () => {
+    const feedPDA = await createTestFeed(
+      ctx,
+      createFeedParams("close-feed-shared", { public: {} }),
+      30 * 86400
+    );
+    await subscribe(feedPDA, 86400);
+
+    try {
+      await closeFeed(feedPDA, null).rpc();
+      assert.fail("Should have failed with ActiveSubscriptions");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("ActiveSubscriptions") ||
+          error.message.includes("custom program error")
+      );
+    }
+
+    // Once the subscription has run out, only the authority's time is refunded
+    const feed = await ctx.molphaProgram.account.feed.fetch(feedPDA);
+    const now = feed.subscribedUntil.toNumber();
+    await setTime(now);
+    const expectedRefund = feed.subscriptionDueTime
+      .subn(now)
+      .mul(feed.pricePerSecondScaled)
+      .div(new BN(1000000))
+      .add(feed.priorityFeeAllowance.sub(feed.consumedPriorityFees));
+
+    const before = await getAccount(ctx.provider.connection, ctx.userTokenAccount);
+    await closeFeed(feedPDA, null).rpc();
+    const after = await getAccount(ctx.provider.connection, ctx.userTokenAccount);
+    assert.equal((after.amount - before.amount).toString(), expectedRefund.toString());
+  });
+
+  it("Refunds no subscriber-funded time after the authority's time ran out", async () => {
+    const feedPDA = await createTestFeed(
+      ctx,
+      createFeedParams("close-feed-outlasted", { public: {} }),
+      86400
+    );
+    await subscribe(feedPDA, 3 * 86400);
+
+    const feed = await ctx.molphaProgram.account.feed.fetch(feedPDA);
+    assert.isTrue(feed.subscribedUntil.gt(feed.subscriptionDueTime));
+    await setTime(feed.subscribedUntil.toNumber());
+
+    // Only the unspent priority fee allowance is left to the authority
+    const expectedRefund = feed.priorityFeeAllowance.sub(feed.consumedPriorityFees);
+    const before = await getAccount(ctx.provider.connection, ctx.userTokenAccount);
+    await closeFeed(feedPDA, null).rpc();
+    const after = await getAccount(ctx.provider.connection, ctx.userTokenAccount);
+    assert.equal((after.amount - before.amount).toString(), expectedRefund.toString());
+  });
+});
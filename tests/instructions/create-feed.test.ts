@@ -10,6 +10,8 @@ import {
   createTestDataSourceInfo,
   createFeedParams,
   getDataSourcePda,
+  decodeFixedString,
+  createForeignMint,
 } from "../setup";
 
 describe("Create Feed Instruction", () => {
@@ -123,10 +125,6 @@ describe("Create Feed Instruction", () => {
         Buffer.from("feed"),
         ctx.authority.publicKey.toBuffer(),
         Buffer.from(feedParams.name),
-        Buffer.from([0]),
-        Buffer.from([feedParams.minSignaturesThreshold]),
-        feedParams.frequency.toBuffer("le", 8),
-        Buffer.from(feedParams.jobId),
       ],
       ctx.molphaProgram.programId
     );
@@ -160,10 +158,10 @@ describe("Create Feed Instruction", () => {
 
     // Verify the feed was created correctly
     assert.ok(feed.authority.equals(ctx.authority.publicKey));
-    assert.deepEqual(feed.feedType, { public: {} });
+    assert.equal(feed.feedType, 0); // FeedType::Public
     assert.equal(feed.minSignaturesThreshold, 2);
     assert.equal(feed.frequency.toNumber(), 300);
-    assert.equal(feed.ipfsCid, "QmTestCID123456789");
+    assert.equal(decodeFixedString(feed.ipfsCid), "QmTestCID123456789");
     assert.deepEqual(feed.dataSource, publicDataSourcePDA);
     // assert.deepEqual(
     //   feed.jobId,
@@ -189,10 +187,6 @@ describe("Create Feed Instruction", () => {
         Buffer.from("feed"),
         ctx.authority.publicKey.toBuffer(),
         Buffer.from(feedParams.name),
-        Buffer.from([1]),
-        Buffer.from([feedParams.minSignaturesThreshold]),
-        feedParams.frequency.toBuffer("le", 8),
-        Buffer.from(feedParams.jobId),
       ],
       ctx.molphaProgram.programId
     );
@@ -225,7 +219,7 @@ describe("Create Feed Instruction", () => {
     const feed = await ctx.molphaProgram.account.feed.fetch(feedPDA);
 
     // Verify the feed was created correctly
-    assert.deepEqual(feed.feedType, { personal: {} });
+    assert.equal(feed.feedType, 1); // FeedType::Personal
     assert.equal(feed.minSignaturesThreshold, 2);
   });
 
@@ -247,10 +241,6 @@ describe("Create Feed Instruction", () => {
         Buffer.from("feed"),
         ctx.authority.publicKey.toBuffer(),
         Buffer.from(feedParams.name),
-        Buffer.from([1]),
-        Buffer.from([feedParams.minSignaturesThreshold]),
-        feedParams.frequency.toBuffer("le", 8),
-        Buffer.from(feedParams.jobId),
       ],
       ctx.molphaProgram.programId
     );
@@ -282,8 +272,10 @@ describe("Create Feed Instruction", () => {
       name: jobId,
       jobId: Array.from(Buffer.from(jobId.padEnd(32, "\0"))),
       feedType: { public: {} },
+      valueType: { i128Price: {} },
       minSignaturesThreshold: 0, // Invalid: should be > 0
       frequency: new anchor.BN(300),
+      updatePolicy: { minUpdateInterval: new anchor.BN(0), deviationThresholdBps: 0, heartbeat: new anchor.BN(0), maxDeviationBps: 0, outlierSignaturesThreshold: 0, emaPeriod: new anchor.BN(0) },
       ipfsCid: "QmTestCID123456789",
     };
 
@@ -292,10 +284,6 @@ describe("Create Feed Instruction", () => {
         Buffer.from("feed"),
         ctx.authority.publicKey.toBuffer(),
         Buffer.from(invalidParams.name),
-        Buffer.from([0]),
-        Buffer.from([invalidParams.minSignaturesThreshold]),
-        invalidParams.frequency.toBuffer("le", 8),
-        Buffer.from(invalidParams.jobId),
       ],
       ctx.molphaProgram.programId
     );
@@ -326,14 +314,64 @@ describe("Create Feed Instruction", () => {
     }
   });
 
+  it("Fails to create feed with zero frequency", async () => {
+    const jobId = "invalid-frequency-feed";
+    const invalidParams = {
+      name: jobId,
+      jobId: Array.from(Buffer.from(jobId.padEnd(32, "\0"))),
+      feedType: { public: {} },
+      valueType: { i128Price: {} },
+      minSignaturesThreshold: 2,
+      frequency: new anchor.BN(0), // Invalid: rounds would time out immediately
+      updatePolicy: { minUpdateInterval: new anchor.BN(0), deviationThresholdBps: 0, heartbeat: new anchor.BN(0), maxDeviationBps: 0, outlierSignaturesThreshold: 0, emaPeriod: new anchor.BN(0) },
+      ipfsCid: "QmTestCID123456789",
+    };
+
+    const [feedPDA] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("feed"),
+        ctx.authority.publicKey.toBuffer(),
+        Buffer.from(invalidParams.name),
+      ],
+      ctx.molphaProgram.programId
+    );
+
+    try {
+      await ctx.molphaProgram.methods
+        .createFeed(invalidParams, new BN(86400), new BN(1000))
+        .accountsPartial({
+          feed: feedPDA,
+          dataSource: publicDataSourcePDA,
+          authority: ctx.authority.publicKey,
+          protocolConfig: ctx.protocolConfigPDA,
+          userTokenAccount: ctx.userTokenAccount,
+          programTokenAccount: ctx.programTokenAccount,
+          underlyingToken: ctx.underlyingTokenMint,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([ctx.authority.payer])
+        .rpc();
+      assert.fail("Should have failed with zero frequency");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("InvalidFeedConfig") ||
+          error.message.includes("custom program error")
+      );
+    }
+  });
+
   it("Fails to create feed with empty IPFS CID", async () => {
     const jobId = "invalid-cid-feed";
     const invalidParams = {
       name: jobId,
       jobId: Array.from(Buffer.from(jobId.padEnd(32, "\0"))),
       feedType: { public: {} },
+      valueType: { i128Price: {} },
       minSignaturesThreshold: 2,
       frequency: new anchor.BN(300),
+      updatePolicy: { minUpdateInterval: new anchor.BN(0), deviationThresholdBps: 0, heartbeat: new anchor.BN(0), maxDeviationBps: 0, outlierSignaturesThreshold: 0, emaPeriod: new anchor.BN(0) },
       ipfsCid: "", // Invalid: should not be empty
     };
 
@@ -342,10 +380,6 @@ describe("Create Feed Instruction", () => {
         Buffer.from("feed"),
         ctx.authority.publicKey.toBuffer(),
         Buffer.from(invalidParams.name),
-        Buffer.from([0]),
-        Buffer.from([invalidParams.minSignaturesThreshold]),
-        invalidParams.frequency.toBuffer("le", 8),
-        Buffer.from(invalidParams.jobId),
       ],
       ctx.molphaProgram.programId
     );
@@ -391,10 +425,6 @@ describe("Create Feed Instruction", () => {
         Buffer.from("feed"),
         ctx.authority.publicKey.toBuffer(),
         Buffer.from(feedParams.name),
-        Buffer.from([0]),
-        Buffer.from([feedParams.minSignaturesThreshold]),
-        feedParams.frequency.toBuffer("le", 8),
-        Buffer.from(feedParams.jobId),
       ],
       ctx.molphaProgram.programId
     );
@@ -424,4 +454,43 @@ describe("Create Feed Instruction", () => {
       );
     }
   });
+
+  it("Fails to fund a feed with a mint other than the protocol token", async () => {
+    const foreign = createForeignMint(ctx);
+    const feedParams = createFeedParams("foreign-mint-feed", { public: {} });
+    const [feedPDA] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("feed"),
+        ctx.authority.publicKey.toBuffer(),
+        Buffer.from(feedParams.name),
+      ],
+      ctx.molphaProgram.programId
+    );
+
+    try {
+      await ctx.molphaProgram.methods
+        .createFeed(feedParams, new BN(86400), new BN(1000))
+        .accountsPartial({
+          feed: feedPDA,
+          dataSource: publicDataSourcePDA,
+          authority: ctx.authority.publicKey,
+          protocolConfig: ctx.protocolConfigPDA,
+          userTokenAccount: foreign.userTokenAccount,
+          programTokenAccount: foreign.programTokenAccount,
+          underlyingToken: foreign.mint,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      assert.fail("Should have failed with ConstraintAddress");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("ConstraintAddress") ||
+          error.message.includes("custom program error"),
+        error.message
+      );
+    }
+    assert.isNull(await ctx.provider.connection.getAccountInfo(feedPDA));
+  });
 });
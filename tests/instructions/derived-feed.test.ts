@@ -0,0 +1,149 @@
+import { assert } from "chai";
+import { PublicKey } from "@solana/web3.js";
+import { BN } from "@coral-xyz/anchor";
+import {
+  setupTestContext,
+  initializeProtocol,
+  TestContext,
+  createFeedParams,
+  createTestFeed,
+  addTestNodes,
+  createAnswerReport,
+  encodeValue,
+  publishTestAnswer,
+} from "../setup";
+
+describe("Derived Feeds", () => {
+  let ctx: TestContext;
+  let ethFeed: PublicKey;
+  let btcFeed: PublicKey;
+  let now: number;
+
+  function getDerivedFeedPda(name: string) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("derived"), ctx.authority.publicKey.toBuffer(), Buffer.from(name)],
+      ctx.molphaProgram.programId
+    )[0];
+  }
+
+  async function createDerivedFeed(
+    name: string,
+    expression: any,
+    sources: PublicKey[],
+    weights: number[],
+    exponent: number,
+    maxStaleness = 3600
+  ) {
+    const derivedFeed = getDerivedFeedPda(name);
+    await ctx.molphaProgram.methods
+      .createDerivedFeed({
+        name,
+        expression,
+        sources,
+        weights: weights.map((weight) => new BN(weight)),
+        exponent,
+        maxStaleness: new BN(maxStaleness),
+      })
+      .accountsPartial({ derivedFeed, authority: ctx.authority.publicKey })
+      .rpc();
+    return derivedFeed;
+  }
+
+  function refreshDerived(derivedFeed: PublicKey, sources: PublicKey[]) {
+    return ctx.molphaProgram.methods
+      .refreshDerived()
+      .accountsPartial({ derivedFeed })
+      .remainingAccounts(
+        sources.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false }))
+      )
+      .rpc();
+  }
+
+  async function expectError(promise: Promise<unknown>, code: string) {
+    try {
+      await promise;
+      assert.fail(`Should have failed with ${code}`);
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes(code) || error.message.includes("custom program error"),
+        error.message
+      );
+    }
+  }
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeProtocol(ctx);
+    await addTestNodes(ctx, 2);
+    ethFeed = await createTestFeed(ctx, createFeedParams("derived-eth-usd", { public: {} }));
+    btcFeed = await createTestFeed(ctx, createFeedParams("derived-btc-usd", { public: {} }));
+
+    const clock = await ctx.provider.context.banksClient.getClock();
+    now = Number(clock.unixTimestamp);
+    // ETH/USD 3000.00 observed at now-20, BTC/USD 60000.00 at now-10
+    await publishTestAnswer(ctx, ethFeed, createAnswerReport(300_000, now - 20, -2));
+    await publishTestAnswer(ctx, btcFeed, createAnswerReport(6_000_000, now - 10, -2));
+  });
+
+  it("Computes a ratio of two feeds", async () => {
+    const derivedFeed = await createDerivedFeed("eth-btc", { ratio: {} }, [ethFeed, btcFeed], [], -6);
+    await refreshDerived(derivedFeed, [ethFeed, btcFeed]);
+
+    const derived = await ctx.molphaProgram.account.derivedFeed.fetch(derivedFeed);
+    // 3000 / 60000 = 0.05
+    assert.deepEqual(derived.latestAnswer.value, encodeValue(50_000));
+    assert.equal(derived.latestAnswer.exponent, -6);
+    assert.equal(derived.latestAnswer.roundId.toNumber(), 1);
+    assert.equal(derived.latestAnswer.observedTo.toNumber(), now - 20);
+    assert.equal(derived.latestAnswer.signerCount, 2);
+
+    const answer = await ctx.molphaProgram.methods
+      .readDerivedAnswer(new BN(3600), 2)
+      .accountsPartial({ derivedFeed })
+      .view();
+    assert.deepEqual(answer.value, encodeValue(50_000));
+  });
+
+  it("Computes a weighted basket", async () => {
+    const derivedFeed = await createDerivedFeed(
+      "eth-btc-basket",
+      { weightedSum: {} },
+      [ethFeed, btcFeed],
+      [5_000, 2_500],
+      0
+    );
+    await refreshDerived(derivedFeed, [ethFeed, btcFeed]);
+
+    const derived = await ctx.molphaProgram.account.derivedFeed.fetch(derivedFeed);
+    // 0.5 * 3000 + 0.25 * 60000
+    assert.deepEqual(derived.latestAnswer.value, encodeValue(16_500));
+  });
+
+  it("Fails with sources in the wrong order", async () => {
+    const derivedFeed = getDerivedFeedPda("eth-btc");
+    await expectError(refreshDerived(derivedFeed, [btcFeed, ethFeed]), "SourcesMismatch");
+  });
+
+  it("Fails when a source is staler than allowed", async () => {
+    const derivedFeed = await createDerivedFeed(
+      "eth-btc-strict",
+      { ratio: {} },
+      [ethFeed, btcFeed],
+      [],
+      -6,
+      15
+    );
+    await expectError(refreshDerived(derivedFeed, [ethFeed, btcFeed]), "StaleAnswer");
+  });
+
+  it("Rejects expressions that do not fit their sources", async () => {
+    await expectError(
+      createDerivedFeed("bad-ratio", { ratio: {} }, [ethFeed], [], -6),
+      "InvalidExpression"
+    );
+    await expectError(
+      createDerivedFeed("bad-basket", { weightedSum: {} }, [ethFeed, btcFeed], [10_000], 0),
+      "InvalidExpression"
+    );
+  });
+});
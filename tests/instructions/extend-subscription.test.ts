@@ -9,6 +9,7 @@ import {
   createTestDataSourceInfo,
   getDataSourcePda,
   createFeedParams,
+  createForeignMint,
 } from "../setup";
 import { Clock } from "solana-bankrun";
 
@@ -73,10 +74,6 @@ describe("Extend Subscription Instruction", () => {
         Buffer.from("feed"),
         ctx.authority.publicKey.toBuffer(),
         Buffer.from(feedParams.name),
-        Buffer.from([0]), // Public feed type
-        Buffer.from([feedParams.minSignaturesThreshold]),
-        feedParams.frequency.toBuffer("le", 8),
-        Buffer.from(feedParams.jobId),
       ],
       ctx.molphaProgram.programId
     );
@@ -227,4 +224,36 @@ describe("Extend Subscription Instruction", () => {
       assert.include(error.message, "Error");
     }
   });
+
+  it("Fails to extend subscription with a mint other than the protocol token", async () => {
+    const foreign = createForeignMint(ctx);
+    const before = await ctx.molphaProgram.account.feed.fetch(feedPDA);
+
+    try {
+      await ctx.molphaProgram.methods
+        .extendSubscription(new anchor.BN(86400), new anchor.BN(1000))
+        .accountsPartial({
+          feed: feedPDA,
+          authority: ctx.authority.publicKey,
+          userTokenAccount: foreign.userTokenAccount,
+          programTokenAccount: foreign.programTokenAccount,
+          protocolConfig: ctx.protocolConfigPDA,
+          underlyingToken: foreign.mint,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      assert.fail("Should have failed with ConstraintAddress");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("ConstraintAddress") ||
+          error.message.includes("custom program error"),
+        error.message
+      );
+    }
+
+    const after = await ctx.molphaProgram.account.feed.fetch(feedPDA);
+    assert.equal(after.balance.toString(), before.balance.toString());
+  });
 });
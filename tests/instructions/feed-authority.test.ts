@@ -0,0 +1,119 @@
+import { assert } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  setupTestContext,
+  initializeProtocol,
+  TestContext,
+  createFeedParams,
+  createTestFeed,
+  getFeedPda,
+} from "../setup";
+
+describe("Feed Authority Transfer", () => {
+  let ctx: TestContext;
+  let feedPDA: PublicKey;
+  const newOwner = Keypair.generate();
+  const params = createFeedParams("feed-authority-test", { personal: {} });
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeProtocol(ctx);
+    feedPDA = await createTestFeed(ctx, params);
+  });
+
+  it("Records the pending authority when proposed", async () => {
+    await ctx.molphaProgram.methods
+      .proposeFeedAuthority(newOwner.publicKey)
+      .accountsPartial({ feed: feedPDA, authority: ctx.authority.publicKey })
+      .rpc();
+
+    const feed = await ctx.molphaProgram.account.feed.fetch(feedPDA);
+    assert.ok(feed.pendingAuthority.equals(newOwner.publicKey));
+    assert.ok(feed.authority.equals(ctx.authority.publicKey));
+  });
+
+  it("Fails when someone other than the pending authority accepts", async () => {
+    const other = Keypair.generate();
+    try {
+      await ctx.molphaProgram.methods
+        .acceptFeedAuthority()
+        .accountsPartial({ feed: feedPDA, newAuthority: other.publicKey })
+        .signers([other])
+        .rpc();
+      assert.fail("Should have failed with NotPendingAuthority");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("NotPendingAuthority") ||
+          error.message.includes("custom program error")
+      );
+    }
+  });
+
+  it("Transfers the feed without changing its address", async () => {
+    await ctx.molphaProgram.methods
+      .acceptFeedAuthority()
+      .accountsPartial({ feed: feedPDA, newAuthority: newOwner.publicKey })
+      .signers([newOwner])
+      .rpc();
+
+    const feed = await ctx.molphaProgram.account.feed.fetch(feedPDA);
+    assert.ok(feed.authority.equals(newOwner.publicKey));
+    assert.ok(feed.pendingAuthority.equals(PublicKey.default));
+    assert.ok(feed.creator.equals(ctx.authority.publicKey));
+    const [derived] = getFeedPda(ctx.molphaProgram.programId, feed.creator, params);
+    assert.ok(derived.equals(feedPDA));
+  });
+
+  it("Rejects owner actions from the previous authority", async () => {
+    try {
+      await ctx.molphaProgram.methods
+        .proposeFeedAuthority(ctx.authority.publicKey)
+        .accountsPartial({ feed: feedPDA, authority: ctx.authority.publicKey })
+        .rpc();
+      assert.fail("Should have failed with has_one constraint");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("ConstraintHasOne") ||
+          error.message.includes("custom program error")
+      );
+    }
+
+    await ctx.molphaProgram.methods
+      .setFeedStatus({ paused: {} })
+      .accountsPartial({
+        feed: feedPDA,
+        authority: newOwner.publicKey,
+        protocolConfig: ctx.protocolConfigPDA,
+        successor: null,
+      })
+      .signers([newOwner])
+      .rpc();
+    const feed = await ctx.molphaProgram.account.feed.fetch(feedPDA);
+    assert.equal(feed.status, 1);
+  });
+
+  it("Cancels a pending transfer by proposing the default key", async () => {
+    const next = Keypair.generate();
+    for (const proposed of [next.publicKey, PublicKey.default]) {
+      await ctx.molphaProgram.methods
+        .proposeFeedAuthority(proposed)
+        .accountsPartial({ feed: feedPDA, authority: newOwner.publicKey })
+        .signers([newOwner])
+        .rpc();
+    }
+
+    try {
+      await ctx.molphaProgram.methods
+        .acceptFeedAuthority()
+        .accountsPartial({ feed: feedPDA, newAuthority: next.publicKey })
+        .signers([next])
+        .rpc();
+      assert.fail("Should have failed with NotPendingAuthority");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("NotPendingAuthority") ||
+          error.message.includes("custom program error")
+      );
+    }
+  });
+});
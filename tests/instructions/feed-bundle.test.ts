@@ -0,0 +1,193 @@
+import { assert } from "chai";
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { BN } from "@coral-xyz/anchor";
+import { createHash } from "crypto";
+import nacl from "tweetnacl";
+import {
+  setupTestContext,
+  initializeProtocol,
+  TestContext,
+  addTestNodes,
+  encodeValue,
+} from "../setup";
+
+interface BundleReport {
+  values: BN[];
+  observedFrom: BN;
+  observedTo: BN;
+}
+
+describe("Feed Bundles", () => {
+  let ctx: TestContext;
+  let bundle: PublicKey;
+  let now: number;
+  const slotNames = Array.from({ length: 60 }, (_, i) => `IDX-${i}`);
+
+  function getFeedBundlePda(name: string) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("bundle"), ctx.authority.publicKey.toBuffer(), Buffer.from(name)],
+      ctx.molphaProgram.programId
+    )[0];
+  }
+
+  // sha256(bundle || borsh(report)), see `BundleReport::signing_message`
+  function bundleMessage(report: BundleReport): Buffer {
+    const len = Buffer.alloc(4);
+    len.writeUInt32LE(report.values.length);
+    return createHash("sha256")
+      .update(bundle.toBuffer())
+      .update(len)
+      .update(Buffer.concat(report.values.map((v) => v.toTwos(64).toArrayLike(Buffer, "le", 8))))
+      .update(report.observedFrom.toTwos(64).toArrayLike(Buffer, "le", 8))
+      .update(report.observedTo.toTwos(64).toArrayLike(Buffer, "le", 8))
+      .digest();
+  }
+
+  function publishBundle(report: BundleReport, signers: Keypair[] = ctx.nodes.slice(0, 2)) {
+    const message = bundleMessage(report);
+    const preIxs = signers.map((signer) =>
+      anchor.web3.Ed25519Program.createInstructionWithPublicKey({
+        publicKey: signer.publicKey.toBytes(),
+        message,
+        signature: nacl.sign.detached(message, signer.secretKey),
+      })
+    );
+    return ctx.molphaProgram.methods
+      .publishBundle(report)
+      .accountsPartial({
+        bundle,
+        nodeRegistry: ctx.nodeRegistryPDA,
+        instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .preInstructions(preIxs)
+      .rpc();
+  }
+
+  function createBundleReport(values: number[], observedTo: number): BundleReport {
+    return {
+      values: values.map((v) => new BN(v)),
+      observedFrom: new BN(observedTo),
+      observedTo: new BN(observedTo),
+    };
+  }
+
+  async function expectError(promise: Promise<unknown>, code: string) {
+    try {
+      await promise;
+      assert.fail(`Should have failed with ${code}`);
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes(code) || error.message.includes("custom program error"),
+        error.message
+      );
+    }
+  }
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeProtocol(ctx);
+    await addTestNodes(ctx, 2);
+
+    const clock = await ctx.provider.context.banksClient.getClock();
+    now = Number(clock.unixTimestamp);
+
+    bundle = getFeedBundlePda("index-constituents");
+    await ctx.molphaProgram.methods
+      .createFeedBundle({
+        name: "index-constituents",
+        minSignaturesThreshold: 2,
+        exponent: -2,
+        capacity: 64,
+      })
+      .accountsPartial({ bundle, authority: ctx.authority.publicKey })
+      .rpc();
+  });
+
+  it("Adds slots in several calls", async () => {
+    for (let i = 0; i < slotNames.length; i += 20) {
+      await ctx.molphaProgram.methods
+        .addBundleSlots(slotNames.slice(i, i + 20))
+        .accountsPartial({ bundle, authority: ctx.authority.publicKey })
+        .rpc();
+    }
+
+    const account = await ctx.molphaProgram.account.feedBundle.fetch(bundle);
+    assert.deepEqual(account.slotNames, slotNames);
+    assert.equal(account.values.length, slotNames.length);
+    assert.equal(account.roundId.toNumber(), 0);
+  });
+
+  it("Publishes every slot under one report", async () => {
+    const values = slotNames.map((_, i) => 10_000 + i);
+    await publishBundle(createBundleReport(values, now - 10));
+
+    const account = await ctx.molphaProgram.account.feedBundle.fetch(bundle);
+    assert.deepEqual(account.values.map((v: BN) => v.toNumber()), values);
+    assert.equal(account.roundId.toNumber(), 1);
+    assert.equal(account.signerCount, 2);
+    assert.equal(account.reportedSlots, slotNames.length);
+
+    const answer = await ctx.molphaProgram.methods
+      .readBundleSlot(42, new BN(3600), 2)
+      .accountsPartial({ bundle })
+      .view();
+    assert.deepEqual(answer.value, encodeValue(10_042));
+    assert.equal(answer.exponent, -2);
+    assert.equal(answer.observedTo.toNumber(), now - 10);
+  });
+
+  it("Fails without one value per slot", async () => {
+    await expectError(
+      publishBundle(createBundleReport([1, 2, 3], now - 5)),
+      "SlotCountMismatch"
+    );
+  });
+
+  it("Fails without a quorum", async () => {
+    const values = slotNames.map(() => 1);
+    await expectError(
+      publishBundle(createBundleReport(values, now - 5), ctx.nodes.slice(0, 1)),
+      "NotEnoughSignatures"
+    );
+  });
+
+  it("Fails to read an unknown slot", async () => {
+    await expectError(
+      ctx.molphaProgram.methods
+        .readBundleSlot(60, new BN(3600), 2)
+        .accountsPartial({ bundle })
+        .view(),
+      "SlotNotFound"
+    );
+  });
+
+  it("Fails to add slots beyond the capacity", async () => {
+    await expectError(
+      ctx.molphaProgram.methods
+        .addBundleSlots(["A", "B", "C", "D", "E"])
+        .accountsPartial({ bundle, authority: ctx.authority.publicKey })
+        .rpc(),
+      "TooManySlots"
+    );
+  });
+
+  it("Fails to read slots added after the latest report", async () => {
+    const readSlot = (index: number) =>
+      ctx.molphaProgram.methods
+        .readBundleSlot(index, new BN(3600), 2)
+        .accountsPartial({ bundle })
+        .view();
+
+    await ctx.molphaProgram.methods
+      .addBundleSlots(["NEW-0", "NEW-1"])
+      .accountsPartial({ bundle, authority: ctx.authority.publicKey })
+      .rpc();
+    await expectError(readSlot(60), "SlotNotReported");
+    assert.deepEqual((await readSlot(42)).value, encodeValue(10_042));
+
+    const values = [...slotNames, "NEW-0", "NEW-1"].map((_, i) => 20_000 + i);
+    await publishBundle(createBundleReport(values, now - 5));
+    assert.deepEqual((await readSlot(60)).value, encodeValue(20_060));
+  });
+});
@@ -0,0 +1,138 @@
+import { assert } from "chai";
+import { PublicKey } from "@solana/web3.js";
+import {
+  setupTestContext,
+  initializeProtocol,
+  TestContext,
+  createFeedParams,
+  createTestFeed,
+  addTestNodes,
+  createAnswerReport,
+  encodeValue,
+  fetchFeedHistory,
+  getFeedHistoryPda,
+  publishTestAnswer,
+} from "../setup";
+
+describe("Feed History", () => {
+  let ctx: TestContext;
+  let feedPDA: PublicKey;
+  let historyPDA: PublicKey;
+  let timestamp: number;
+
+  async function publish(value: number) {
+    timestamp += 1;
+    await publishTestAnswer(
+      ctx,
+      feedPDA,
+      createAnswerReport(value, timestamp),
+      ctx.nodes.slice(0, 2),
+      historyPDA
+    );
+  }
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeProtocol(ctx);
+    await addTestNodes(ctx, 2);
+
+    feedPDA = await createTestFeed(ctx, createFeedParams("history-feed-test", { public: {} }));
+    [historyPDA] = getFeedHistoryPda(ctx.molphaProgram.programId, feedPDA);
+
+    const clock = await ctx.provider.context.banksClient.getClock();
+    timestamp = Number(clock.unixTimestamp) - 100;
+  });
+
+  it("Creates a history account with the chosen capacity", async () => {
+    await ctx.molphaProgram.methods
+      .createFeedHistory(3)
+      .accountsPartial({
+        feed: feedPDA,
+        history: historyPDA,
+        authority: ctx.authority.publicKey,
+      })
+      .rpc();
+
+    const feed = await ctx.molphaProgram.account.feed.fetch(feedPDA);
+    assert.ok(feed.history.equals(historyPDA));
+
+    const history = await fetchFeedHistory(ctx, historyPDA);
+    assert.equal(history.capacity, 3);
+    assert.equal(history.len, 0);
+    assert.equal(history.space, 8 + 48 + 3 * 80);
+  });
+
+  it("Fails to publish without the history account", async () => {
+    try {
+      await publishTestAnswer(
+        ctx,
+        feedPDA,
+        createAnswerReport(1, timestamp + 1)
+      );
+      assert.fail("Should have failed with InvalidHistoryAccount");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("InvalidHistoryAccount") ||
+          error.message.includes("custom program error")
+      );
+    }
+  });
+
+  it("Keeps the latest answers once full, oldest first", async () => {
+    for (let value = 1; value <= 4; value++) {
+      await publish(value);
+    }
+
+    const history = await fetchFeedHistory(ctx, historyPDA);
+    assert.equal(history.len, 3);
+    assert.deepEqual(
+      history.entries.map((entry) => entry.value),
+      [2, 3, 4].map((value) => encodeValue(value))
+    );
+    assert.deepEqual(
+      history.entries.map((entry) => entry.roundId.toNumber()),
+      [2, 3, 4]
+    );
+  });
+
+  it("Grows the buffer and keeps the recorded answers", async () => {
+    await ctx.molphaProgram.methods
+      .growFeedHistory(5)
+      .accountsPartial({
+        feed: feedPDA,
+        history: historyPDA,
+        authority: ctx.authority.publicKey,
+      })
+      .rpc();
+
+    await publish(5);
+    await publish(6);
+
+    const history = await fetchFeedHistory(ctx, historyPDA);
+    assert.equal(history.capacity, 5);
+    assert.equal(history.space, 8 + 48 + 5 * 80);
+    assert.deepEqual(
+      history.entries.map((entry) => entry.value),
+      [2, 3, 4, 5, 6].map((value) => encodeValue(value))
+    );
+  });
+
+  it("Fails to shrink the buffer", async () => {
+    try {
+      await ctx.molphaProgram.methods
+        .growFeedHistory(4)
+        .accountsPartial({
+          feed: feedPDA,
+          history: historyPDA,
+          authority: ctx.authority.publicKey,
+        })
+        .rpc();
+      assert.fail("Should have failed with InvalidHistoryCapacity");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("InvalidHistoryCapacity") ||
+          error.message.includes("custom program error")
+      );
+    }
+  });
+});
@@ -0,0 +1,185 @@
+import * as anchor from "@coral-xyz/anchor";
+import { assert } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { BN } from "@coral-xyz/anchor";
+import {
+  setupTestContext,
+  initializeProtocol,
+  TestContext,
+  createFeedParams,
+  createTestFeed,
+  addTestNodes,
+  createAnswerReport,
+  publishTestAnswer,
+  getFeedHistoryPda,
+} from "../setup";
+import { Clock } from "solana-bankrun";
+
+describe("Feed Reader Allow-List", () => {
+  let ctx: TestContext;
+  let feedPDA: PublicKey;
+  let readersPDA: PublicKey;
+
+  function setReaders(feed: PublicKey, readers: PublicKey[]) {
+    const [readersAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("readers"), feed.toBuffer()],
+      ctx.molphaProgram.programId
+    );
+    return ctx.molphaProgram.methods
+      .setFeedReaders(readers)
+      .accountsPartial({
+        feed,
+        readers: readersAccount,
+        authority: ctx.authority.publicKey,
+      })
+      .rpc();
+  }
+
+  function readAnswer() {
+    return ctx.molphaProgram.methods
+      .readAnswer(new BN(3600), 1)
+      .accountsPartial({ feed: feedPDA })
+      .view();
+  }
+
+  function readAnswerGated() {
+    return ctx.molphaProgram.methods
+      .readAnswerGated(new BN(3600), 1)
+      .accountsPartial({
+        feed: feedPDA,
+        readers: readersPDA,
+        instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .view();
+  }
+
+  async function expectReaderNotAllowed(read: () => Promise<unknown>) {
+    try {
+      await read();
+      assert.fail("Should have failed with ReaderNotAllowed");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("ReaderNotAllowed") ||
+          error.message.includes("custom program error") ||
+          error.message.includes("Simulation failed")
+      );
+    }
+  }
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeProtocol(ctx);
+    await addTestNodes(ctx, 2);
+    feedPDA = await createTestFeed(ctx, createFeedParams("readers-feed-test", { personal: {} }));
+    [readersPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("readers"), feedPDA.toBuffer()],
+      ctx.molphaProgram.programId
+    );
+
+    const clock = await ctx.provider.context.banksClient.getClock();
+    await publishTestAnswer(ctx, feedPDA, createAnswerReport(100, Number(clock.unixTimestamp)));
+  });
+
+  it("Restricts reads to the allowed programs", async () => {
+    const consumerProgram = Keypair.generate().publicKey;
+    await setReaders(feedPDA, [consumerProgram]);
+
+    const readers = await ctx.molphaProgram.account.feedReaders.fetch(readersPDA);
+    assert.ok(readers.feed.equals(feedPDA));
+    assert.equal(readers.readers.length, 1);
+    assert.ok(readers.readers[0].equals(consumerProgram));
+    const feed = await ctx.molphaProgram.account.feed.fetch(feedPDA);
+    assert.equal(feed.restrictedReaders, 1);
+
+    await expectReaderNotAllowed(readAnswer);
+    // Called directly, the top-level program is Molpha, which is not allowed
+    await expectReaderNotAllowed(readAnswerGated);
+  });
+
+  it("Allows reads from a program in the list", async () => {
+    await setReaders(feedPDA, [ctx.molphaProgram.programId]);
+    const answer = await readAnswerGated();
+    assert.equal(answer.signerCount, 2);
+  });
+
+  it("Applies the list to history reads", async () => {
+    const [historyPDA] = getFeedHistoryPda(ctx.molphaProgram.programId, feedPDA);
+    await ctx.molphaProgram.methods
+      .createFeedHistory(4)
+      .accountsPartial({
+        feed: feedPDA,
+        history: historyPDA,
+        authority: ctx.authority.publicKey,
+      })
+      .rpc();
+    const clock = await ctx.provider.context.banksClient.getClock();
+    const now = clock.unixTimestamp + 10n;
+    ctx.provider.context.setClock(
+      new Clock(clock.slot, clock.epochStartTimestamp, clock.epoch, clock.leaderScheduleEpoch, now)
+    );
+    await publishTestAnswer(
+      ctx,
+      feedPDA,
+      createAnswerReport(101, Number(now)),
+      ctx.nodes.slice(0, 2),
+      historyPDA
+    );
+
+    const getRound = (readers: PublicKey | null) =>
+      ctx.molphaProgram.methods
+        .getRound(new BN(2))
+        .accountsPartial({
+          feed: feedPDA,
+          history: historyPDA,
+          readers,
+          instructions: readers && anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .view();
+
+    await expectReaderNotAllowed(() => getRound(null));
+    await setReaders(feedPDA, [Keypair.generate().publicKey]);
+    await expectReaderNotAllowed(() => getRound(readersPDA));
+
+    await setReaders(feedPDA, [ctx.molphaProgram.programId]);
+    const answer = await getRound(readersPDA);
+    assert.equal(answer.answer.signerCount, 2);
+  });
+
+  it("Lifts the restriction with an empty list", async () => {
+    await setReaders(feedPDA, []);
+    const feed = await ctx.molphaProgram.account.feed.fetch(feedPDA);
+    assert.equal(feed.restrictedReaders, 0);
+    const answer = await readAnswer();
+    assert.equal(answer.signerCount, 2);
+  });
+
+  it("Fails for public feeds", async () => {
+    const publicFeed = await createTestFeed(
+      ctx,
+      createFeedParams("readers-public-feed", { public: {} })
+    );
+    try {
+      await setReaders(publicFeed, [Keypair.generate().publicKey]);
+      assert.fail("Should have failed with NotSupported");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("NotSupported") ||
+          error.message.includes("custom program error")
+      );
+    }
+  });
+
+  it("Fails with more than 16 readers", async () => {
+    const readers = Array.from({ length: 17 }, () => Keypair.generate().publicKey);
+    try {
+      await setReaders(feedPDA, readers);
+      assert.fail("Should have failed with TooManyReaders");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("TooManyReaders") ||
+          error.message.includes("custom program error") ||
+          error.message.includes("too large")
+      );
+    }
+  });
+});
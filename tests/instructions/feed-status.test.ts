@@ -0,0 +1,189 @@
+import { assert } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { BN } from "@coral-xyz/anchor";
+import {
+  setupTestContext,
+  initializeProtocol,
+  TestContext,
+  createFeedParams,
+  createTestFeed,
+  addTestNodes,
+  createAnswerReport,
+  publishTestAnswer,
+} from "../setup";
+
+describe("Feed Status", () => {
+  let ctx: TestContext;
+  let feedPDA: PublicKey;
+  // Feed authority, distinct from the protocol admin (ctx.authority)
+  const owner = Keypair.generate();
+
+  function getSuccessorPda(feed: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("successor"), feed.toBuffer()],
+      ctx.molphaProgram.programId
+    )[0];
+  }
+
+  function setStatus(feed: PublicKey, status: any, signer: Keypair | null) {
+    const builder = ctx.molphaProgram.methods.setFeedStatus(status).accountsPartial({
+      feed,
+      authority: signer ? signer.publicKey : ctx.authority.publicKey,
+      protocolConfig: ctx.protocolConfigPDA,
+      successor: status.deprecated ? getSuccessorPda(feed) : null,
+    });
+    return signer ? builder.signers([signer]).rpc() : builder.rpc();
+  }
+
+  function fetchSuccessor(feed: PublicKey) {
+    return ctx.molphaProgram.account.feedSuccessor.fetch(getSuccessorPda(feed));
+  }
+
+  function readAnswer(feed: PublicKey) {
+    return ctx.molphaProgram.methods
+      .readAnswer(new BN(3600), 1)
+      .accountsPartial({ feed })
+      .view();
+  }
+
+  async function expectError(promise: Promise<unknown>, code: string) {
+    try {
+      await promise;
+      assert.fail(`Should have failed with ${code}`);
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes(code) ||
+          error.message.includes("custom program error") ||
+          error.message.includes("Simulation failed"),
+        error.message
+      );
+    }
+  }
+
+  async function createOwnedFeed(name: string) {
+    const feed = await createTestFeed(ctx, createFeedParams(name, { public: {} }));
+    await ctx.molphaProgram.methods
+      .proposeFeedAuthority(owner.publicKey)
+      .accountsPartial({ feed, authority: ctx.authority.publicKey })
+      .rpc();
+    await ctx.molphaProgram.methods
+      .acceptFeedAuthority()
+      .accountsPartial({ feed, newAuthority: owner.publicKey })
+      .signers([owner])
+      .rpc();
+    return feed;
+  }
+
+  async function now() {
+    const clock = await ctx.provider.context.banksClient.getClock();
+    return Number(clock.unixTimestamp);
+  }
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeProtocol(ctx);
+    await addTestNodes(ctx, 2);
+    feedPDA = await createOwnedFeed("status-feed-test");
+    await publishTestAnswer(ctx, feedPDA, createAnswerReport(100, (await now()) - 10));
+  });
+
+  it("Deprecates a feed with a successor and keeps it readable", async () => {
+    const successor = Keypair.generate().publicKey;
+    await setStatus(feedPDA, { deprecated: { successor } }, owner);
+
+    const feed = await ctx.molphaProgram.account.feed.fetch(feedPDA);
+    assert.equal(feed.status, 2);
+    const record = await fetchSuccessor(feedPDA);
+    assert.ok(record.feed.equals(feedPDA));
+    assert.ok(record.successor.equals(successor));
+    const answer = await readAnswer(feedPDA);
+    assert.equal(answer.signerCount, 2);
+
+    // Answers are still accepted
+    await publishTestAnswer(ctx, feedPDA, createAnswerReport(101, (await now()) - 5));
+  });
+
+  it("Replaces the successor of a deprecated feed", async () => {
+    const successor = Keypair.generate().publicKey;
+    await setStatus(feedPDA, { deprecated: { successor } }, owner);
+    assert.ok((await fetchSuccessor(feedPDA)).successor.equals(successor));
+  });
+
+  it("Requires the successor record to deprecate", async () => {
+    const successor = Keypair.generate().publicKey;
+    await expectError(
+      ctx.molphaProgram.methods
+        .setFeedStatus({ deprecated: { successor } })
+        .accountsPartial({
+          feed: feedPDA,
+          authority: owner.publicKey,
+          protocolConfig: ctx.protocolConfigPDA,
+          successor: null,
+        })
+        .signers([owner])
+        .rpc(),
+      "InvalidStatusTransition"
+    );
+  });
+
+  it("Rejects the feed itself as successor", async () => {
+    await expectError(
+      setStatus(feedPDA, { deprecated: { successor: feedPDA } }, owner),
+      "InvalidStatusTransition"
+    );
+  });
+
+  it("Does not let the owner reactivate a deprecated feed", async () => {
+    await expectError(setStatus(feedPDA, { active: {} }, owner), "InvalidStatusTransition");
+  });
+
+  it("Rejects anyone but the owner and the admin", async () => {
+    await expectError(setStatus(feedPDA, { retired: {} }, Keypair.generate()), "Unauthorized");
+  });
+
+  it("Retires a deprecated feed for good", async () => {
+    await setStatus(feedPDA, { retired: {} }, owner);
+    const feed = await ctx.molphaProgram.account.feed.fetch(feedPDA);
+    assert.equal(feed.status, 3);
+    // Consumers of the retired feed can still find its replacement
+    assert.ok((await fetchSuccessor(feedPDA)).feed.equals(feedPDA));
+
+    await expectError(readAnswer(feedPDA), "FeedRetired");
+    await expectError(
+      publishTestAnswer(ctx, feedPDA, createAnswerReport(102, await now())),
+      "FeedRetired"
+    );
+    // Not even the admin can bring it back
+    await expectError(setStatus(feedPDA, { active: {} }, null), "InvalidStatusTransition");
+  });
+
+  it("Lets the admin pause any feed", async () => {
+    const feed = await createOwnedFeed("status-admin-feed");
+    await expectError(setStatus(feed, { retired: {} }, owner), "InvalidStatusTransition");
+
+    await setStatus(feed, { paused: {} }, null);
+    const paused = await ctx.molphaProgram.account.feed.fetch(feed);
+    assert.equal(paused.status, 1);
+    assert.equal(paused.pausedByAdmin, 1);
+    await expectError(readAnswer(feed), "FeedPaused");
+
+    // Only the admin lifts an admin pause
+    await expectError(setStatus(feed, { active: {} }, owner), "Unauthorized");
+    await expectError(
+      setStatus(feed, { deprecated: { successor: Keypair.generate().publicKey } }, owner),
+      "Unauthorized"
+    );
+    await setStatus(feed, { active: {} }, null);
+    const resumed = await ctx.molphaProgram.account.feed.fetch(feed);
+    assert.equal(resumed.status, 0);
+    assert.equal(resumed.pausedByAdmin, 0);
+  });
+
+  it("Lets the owner lift its own pause", async () => {
+    const feed = await createOwnedFeed("status-owner-pause");
+    await setStatus(feed, { paused: {} }, owner);
+    assert.equal((await ctx.molphaProgram.account.feed.fetch(feed)).pausedByAdmin, 0);
+    await setStatus(feed, { active: {} }, owner);
+    assert.equal((await ctx.molphaProgram.account.feed.fetch(feed)).status, 0);
+  });
+});
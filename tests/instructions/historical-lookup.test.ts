@@ -0,0 +1,150 @@
+import { assert } from "chai";
+import { PublicKey } from "@solana/web3.js";
+import { BN } from "@coral-xyz/anchor";
+import {
+  setupTestContext,
+  initializeProtocol,
+  TestContext,
+  createFeedParams,
+  createTestFeed,
+  addTestNodes,
+  createAnswerReport,
+  encodeValue,
+  getFeedHistoryPda,
+  publishTestAnswer,
+} from "../setup";
+
+describe("Historical Lookup", () => {
+  let ctx: TestContext;
+  let feedPDA: PublicKey;
+  let historyPDA: PublicKey;
+  let start: number;
+  let now: number;
+
+  function getAnswerAt(timestamp: number) {
+    return ctx.molphaProgram.methods
+      .getAnswerAt(new BN(timestamp))
+      .accountsPartial({
+        feed: feedPDA,
+        history: historyPDA,
+        readers: null,
+        instructions: null,
+      })
+      .view();
+  }
+
+  function getRound(roundId: number) {
+    return ctx.molphaProgram.methods
+      .getRound(new BN(roundId))
+      .accountsPartial({
+        feed: feedPDA,
+        history: historyPDA,
+        readers: null,
+        instructions: null,
+      })
+      .view();
+  }
+
+  async function expectError(promise: Promise<unknown>, name: string) {
+    try {
+      await promise;
+      assert.fail(`Should have failed with ${name}`);
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes(name) ||
+          error.message.includes("custom program error") ||
+          error.message.includes("Simulation failed")
+      );
+    }
+  }
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeProtocol(ctx);
+    await addTestNodes(ctx, 2);
+
+    feedPDA = await createTestFeed(ctx, createFeedParams("lookup-feed-test", { public: {} }));
+    [historyPDA] = getFeedHistoryPda(ctx.molphaProgram.programId, feedPDA);
+    await ctx.molphaProgram.methods
+      .createFeedHistory(3)
+      .accountsPartial({
+        feed: feedPDA,
+        history: historyPDA,
+        authority: ctx.authority.publicKey,
+      })
+      .rpc();
+
+    const clock = await ctx.provider.context.banksClient.getClock();
+    now = Number(clock.unixTimestamp);
+    start = now - 100;
+
+    // Rounds 1..4 observed every 10 seconds; round 1 is overwritten
+    for (let round = 1; round <= 4; round++) {
+      await publishTestAnswer(
+        ctx,
+        feedPDA,
+        createAnswerReport(round * 100, start + round * 10),
+        ctx.nodes.slice(0, 2),
+        historyPDA
+      );
+    }
+  });
+
+  it("Returns the answer in effect at a timestamp with its age", async () => {
+    const result = await getAnswerAt(start + 35);
+    assert.equal(result.answer.roundId.toNumber(), 3);
+    assert.deepEqual(result.answer.value, encodeValue(300));
+    assert.equal(result.age.toNumber(), 5);
+  });
+
+  it("Returns an answer observed exactly at the timestamp", async () => {
+    const result = await getAnswerAt(start + 20);
+    assert.equal(result.answer.roundId.toNumber(), 2);
+    assert.equal(result.age.toNumber(), 0);
+  });
+
+  it("Returns the latest answer for recent timestamps", async () => {
+    const result = await getAnswerAt(now);
+    assert.equal(result.answer.roundId.toNumber(), 4);
+    assert.equal(result.age.toNumber(), now - (start + 40));
+  });
+
+  it("Fails before the recorded history", async () => {
+    await expectError(getAnswerAt(start + 15), "AnswerNotFound");
+  });
+
+  it("Fails for future timestamps", async () => {
+    await expectError(getAnswerAt(now + 3600), "FutureTimestamp");
+  });
+
+  it("Returns a round by ID", async () => {
+    const result = await getRound(2);
+    assert.deepEqual(result.answer.value, encodeValue(200));
+    assert.ok(result.age.toNumber() >= now - (start + 20));
+  });
+
+  it("Fails for rounds no longer in the history", async () => {
+    await expectError(getRound(1), "AnswerNotFound");
+    await expectError(getRound(5), "AnswerNotFound");
+  });
+
+  it("Fails while the feed is paused", async () => {
+    const setStatus = (status: any) =>
+      ctx.molphaProgram.methods
+        .setFeedStatus(status)
+        .accountsPartial({
+          feed: feedPDA,
+          authority: ctx.authority.publicKey,
+          protocolConfig: ctx.protocolConfigPDA,
+          successor: null,
+        })
+        .rpc();
+
+    await setStatus({ paused: {} });
+    await expectError(getAnswerAt(start + 35), "FeedPaused");
+    await expectError(getRound(2), "FeedPaused");
+    await setStatus({ active: {} });
+    const result = await getRound(2);
+    assert.equal(result.answer.roundId.toNumber(), 2);
+  });
+});
@@ -0,0 +1,234 @@
+import { assert } from "chai";
+import { PublicKey } from "@solana/web3.js";
+import { BN } from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import {
+  setupTestContext,
+  initializeProtocol,
+  TestContext,
+  createFeedParams,
+  createTestFeed,
+  decodeFixedString,
+  getFeedPda,
+} from "../setup";
+
+// Offset of `Feed::name`, after the discriminator
+const NAME_OFFSET = 8 + 240;
+
+describe("Migrate Feed Address", () => {
+  let ctx: TestContext;
+  let oldFeedPDA: PublicKey;
+  const params = createFeedParams("old-address-feed", { public: {} });
+
+  // Address of a feed under the previous seeds, which included mutable config
+  function getOldFeedPda(creator: PublicKey, feedParams = params, feedType = 0) {
+    return PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("feed"),
+        creator.toBuffer(),
+        Buffer.from(feedParams.name),
+        Buffer.from([feedType]),
+        Buffer.from([feedParams.minSignaturesThreshold]),
+        feedParams.frequency.toBuffer("le", 8),
+        Buffer.from(feedParams.jobId),
+      ],
+      ctx.molphaProgram.programId
+    );
+  }
+
+  function getReadersPda(feed: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("readers"), feed.toBuffer()],
+      ctx.molphaProgram.programId
+    );
+  }
+
+  function migrate(oldFeed: PublicKey, name: string) {
+    const [newFeed] = getFeedPda(ctx.molphaProgram.programId, ctx.authority.publicKey, {
+      ...params,
+      name,
+    });
+    return ctx.molphaProgram.methods
+      .migrateFeedAddress(name)
+      .accountsPartial({
+        oldFeed,
+        newFeed,
+        history: null,
+        oldReaders: null,
+        newReaders: null,
+        authority: ctx.authority.publicKey,
+      })
+      .rpc();
+  }
+
+  async function expectError(promise: Promise<unknown>, code: string) {
+    try {
+      await promise;
+      assert.fail(`Should have failed with ${code}`);
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes(code) || error.message.includes("custom program error"),
+        error.message
+      );
+    }
+  }
+
+  // Copies a feed to its previous address under `params.name`
+  async function copyToOldAddress(template: PublicKey, oldAddress: PublicKey) {
+    const account = await ctx.provider.connection.getAccountInfo(template);
+    const data = Buffer.from(account!.data);
+    data.fill(0, NAME_OFFSET, NAME_OFFSET + 32);
+    data.write(params.name, NAME_OFFSET);
+
+    ctx.provider.context.setAccount(oldAddress, {
+      lamports: account!.lamports,
+      data,
+      owner: ctx.molphaProgram.programId,
+      executable: false,
+    });
+  }
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeProtocol(ctx);
+
+    const template = await createTestFeed(
+      ctx,
+      createFeedParams("address-template-feed", { public: {} })
+    );
+    [oldFeedPDA] = getOldFeedPda(ctx.authority.publicKey);
+    await copyToOldAddress(template, oldFeedPDA);
+  });
+
+  it("Moves the feed to its creator and name address", async () => {
+    const [newFeedPDA] = getFeedPda(ctx.molphaProgram.programId, ctx.authority.publicKey, params);
+    const oldFeed = await ctx.molphaProgram.account.feed.fetch(oldFeedPDA);
+
+    await ctx.molphaProgram.methods
+      .migrateFeedAddress(params.name)
+      .accountsPartial({
+        oldFeed: oldFeedPDA,
+        newFeed: newFeedPDA,
+        history: null,
+        oldReaders: null,
+        newReaders: null,
+        authority: ctx.authority.publicKey,
+      })
+      .rpc();
+
+    const newFeed = await ctx.molphaProgram.account.feed.fetch(newFeedPDA);
+    assert.equal(decodeFixedString(newFeed.name), params.name);
+    assert.ok(newFeed.authority.equals(oldFeed.authority));
+    assert.equal(newFeed.balance.toString(), oldFeed.balance.toString());
+    assert.equal(
+      newFeed.subscriptionDueTime.toString(),
+      oldFeed.subscriptionDueTime.toString()
+    );
+    assert.isNull(await ctx.provider.connection.getAccountInfo(oldFeedPDA));
+  });
+
+  it("Migrates a colliding feed under a new name with its readers", async () => {
+    // Same name as the feed migrated above, with a different config
+    const template = await createTestFeed(
+      ctx,
+      createFeedParams("readers-template-feed", { personal: {} })
+    );
+    const collidingParams = { ...params, frequency: params.frequency.addn(60) };
+    const [collidingPDA] = getOldFeedPda(ctx.authority.publicKey, collidingParams, 1);
+    await copyToOldAddress(template, collidingPDA);
+
+    const [oldReadersPDA] = getReadersPda(collidingPDA);
+    const reader = PublicKey.unique();
+    await ctx.molphaProgram.methods
+      .setFeedReaders([reader])
+      .accountsPartial({
+        feed: collidingPDA,
+        readers: oldReadersPDA,
+        authority: ctx.authority.publicKey,
+      })
+      .rpc();
+
+    const renamed = { ...params, name: "old-address-feed-2" };
+    const [newFeedPDA] = getFeedPda(ctx.molphaProgram.programId, ctx.authority.publicKey, renamed);
+    const [newReadersPDA] = getReadersPda(newFeedPDA);
+
+    // The reader list must move with a restricted feed
+    try {
+      await ctx.molphaProgram.methods
+        .migrateFeedAddress(renamed.name)
+        .accountsPartial({
+          oldFeed: collidingPDA,
+          newFeed: newFeedPDA,
+          history: null,
+          oldReaders: null,
+          newReaders: null,
+          authority: ctx.authority.publicKey,
+        })
+        .rpc();
+      assert.fail("Should have failed with InvalidReadersAccount");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("InvalidReadersAccount") ||
+          error.message.includes("custom program error")
+      );
+    }
+
+    await ctx.molphaProgram.methods
+      .migrateFeedAddress(renamed.name)
+      .accountsPartial({
+        oldFeed: collidingPDA,
+        newFeed: newFeedPDA,
+        history: null,
+        oldReaders: oldReadersPDA,
+        newReaders: newReadersPDA,
+        authority: ctx.authority.publicKey,
+      })
+      .rpc();
+
+    const newFeed = await ctx.molphaProgram.account.feed.fetch(newFeedPDA);
+    assert.equal(decodeFixedString(newFeed.name), renamed.name);
+    assert.equal(newFeed.restrictedReaders, 1);
+    const readers = await ctx.molphaProgram.account.feedReaders.fetch(newReadersPDA);
+    assert.ok(readers.feed.equals(newFeedPDA));
+    assert.deepEqual(readers.readers.map((r) => r.toBase58()), [reader.toBase58()]);
+    assert.isNull(await ctx.provider.connection.getAccountInfo(oldReadersPDA));
+  });
+
+  it("Rejects feeds already at their creator and name address", async () => {
+    const [currentPDA] = getFeedPda(ctx.molphaProgram.programId, ctx.authority.publicKey, params);
+    await expectError(migrate(currentPDA, "old-address-feed-3"), "FeedAlreadyMigrated");
+    assert.isNotNull(await ctx.provider.connection.getAccountInfo(currentPDA));
+  });
+
+  it("Rejects feeds with running subscriptions", async () => {
+    const template = await createTestFeed(
+      ctx,
+      createFeedParams("subscribed-template-feed", { public: {} })
+    );
+    const [subscription] = PublicKey.findProgramAddressSync(
+      [Buffer.from("subscription"), template.toBuffer(), ctx.authority.publicKey.toBuffer()],
+      ctx.molphaProgram.programId
+    );
+    await ctx.molphaProgram.methods
+      .subscribeToFeed(new BN(3600))
+      .accountsPartial({
+        feed: template,
+        subscription,
+        subscriber: ctx.authority.publicKey,
+        subscriberTokenAccount: ctx.userTokenAccount,
+        programTokenAccount: ctx.programTokenAccount,
+        protocolConfig: ctx.protocolConfigPDA,
+        underlyingToken: ctx.underlyingTokenMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const subscribedParams = { ...params, frequency: params.frequency.addn(120) };
+    const [subscribedPDA] = getOldFeedPda(ctx.authority.publicKey, subscribedParams);
+    await copyToOldAddress(template, subscribedPDA);
+
+    await expectError(migrate(subscribedPDA, "old-address-feed-4"), "ActiveSubscriptions");
+    assert.isNotNull(await ctx.provider.connection.getAccountInfo(subscribedPDA));
+  });
+});
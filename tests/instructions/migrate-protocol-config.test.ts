@@ -0,0 +1,71 @@
+import { assert } from "chai";
+import {
+  setupTestContext,
+  initializeProtocol,
+  TestContext,
+} from "../setup";
+
+// Discriminator and fields of the protocol config before the transmitter
+// schedule, followed by the bump (see `PROTOCOL_CONFIG_V1_SPACE`)
+const V1_FIELDS_LEN = 8 + 107;
+
+describe("Migrate Protocol Config Instruction", () => {
+  let ctx: TestContext;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeProtocol(ctx);
+
+    // Rewrite the config in the V1 layout, with a recognizable base price
+    const account = await ctx.provider.connection.getAccountInfo(ctx.protocolConfigPDA);
+    const current = Buffer.from(account!.data);
+    const data = Buffer.concat([
+      current.subarray(0, V1_FIELDS_LEN),
+      current.subarray(current.length - 1),
+    ]);
+    data.writeBigUInt64LE(BigInt(1234), 8 + 64);
+    ctx.provider.context.setAccount(ctx.protocolConfigPDA, {
+      lamports: account!.lamports,
+      data,
+      owner: ctx.molphaProgram.programId,
+      executable: false,
+    });
+  });
+
+  it("Resizes a V1 config and keeps its fields", async () => {
+    await ctx.molphaProgram.methods
+      .migrateProtocolConfig()
+      .accountsPartial({
+        protocolConfig: ctx.protocolConfigPDA,
+        payer: ctx.authority.publicKey,
+      })
+      .rpc();
+
+    const config = await ctx.molphaProgram.account.protocolConfig.fetch(ctx.protocolConfigPDA);
+    assert.ok(config.authority.equals(ctx.authority.publicKey));
+    assert.ok(config.underlyingToken.equals(ctx.underlyingTokenMint));
+    assert.equal(config.basePricePerSecondScaled.toNumber(), 1234);
+    assert.equal(config.transmitterWindow.toNumber(), 0);
+    assert.equal(config.gracePeriod.toNumber(), 0);
+    assert.notEqual(config.bump, 0);
+  });
+
+  it("Fails on a config with the current layout", async () => {
+    try {
+      await ctx.molphaProgram.methods
+        .migrateProtocolConfig()
+        .accountsPartial({
+          protocolConfig: ctx.protocolConfigPDA,
+          payer: ctx.authority.publicKey,
+        })
+        .rpc();
+      assert.fail("Should have failed with ProtocolConfigUpToDate");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("ProtocolConfigUpToDate") ||
+          error.message.includes("custom program error"),
+        error.message
+      );
+    }
+  });
+});
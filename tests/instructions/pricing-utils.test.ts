@@ -78,10 +78,6 @@ describe("Pricing Utility Tests", () => {
           Buffer.from("feed"),
           ctx.authority.publicKey.toBuffer(),
           Buffer.from(feedParams.name),
-          Buffer.from([0]), // Public feed type
-          Buffer.from([feedParams.minSignaturesThreshold]),
-          feedParams.frequency.toBuffer("le", 8),
-          Buffer.from(feedParams.jobId),
         ],
         ctx.molphaProgram.programId
       );
@@ -155,8 +151,10 @@ describe("Pricing Utility Tests", () => {
         name: highFreqJobId,
         jobId: Array.from(Buffer.from(highFreqJobId.padEnd(32, "\0"))),
         feedType: { public: {} },
+        valueType: { i128Price: {} },
         minSignaturesThreshold: 5, // Higher than default 2
         frequency: new anchor.BN(60), // 1 minute instead of 5 minutes
+        updatePolicy: { minUpdateInterval: new anchor.BN(0), deviationThresholdBps: 0, heartbeat: new anchor.BN(0), maxDeviationBps: 0, outlierSignaturesThreshold: 0, emaPeriod: new anchor.BN(0) },
         ipfsCid: "QmTestCID123456789",
       };
 
@@ -165,10 +163,6 @@ describe("Pricing Utility Tests", () => {
           Buffer.from("feed"),
           ctx.authority.publicKey.toBuffer(),
           Buffer.from(highFreqFeedParams.name),
-          Buffer.from([0]), // Public feed type
-          Buffer.from([highFreqFeedParams.minSignaturesThreshold]),
-          highFreqFeedParams.frequency.toBuffer("le", 8),
-          Buffer.from(highFreqFeedParams.jobId),
         ],
         ctx.molphaProgram.programId
       );
@@ -254,10 +248,6 @@ describe("Pricing Utility Tests", () => {
           Buffer.from("feed"),
           ctx.authority.publicKey.toBuffer(),
           Buffer.from(feedParams.name),
-          Buffer.from([0]), // Public feed type
-          Buffer.from([feedParams.minSignaturesThreshold]),
-          feedParams.frequency.toBuffer("le", 8),
-          Buffer.from(feedParams.jobId),
         ],
         ctx.molphaProgram.programId
       );
@@ -320,10 +310,6 @@ describe("Pricing Utility Tests", () => {
           Buffer.from("feed"),
           ctx.authority.publicKey.toBuffer(),
           Buffer.from(feedParams.name),
-          Buffer.from([0]), // Public feed type
-          Buffer.from([feedParams.minSignaturesThreshold]),
-          feedParams.frequency.toBuffer("le", 8),
-          Buffer.from(feedParams.jobId),
         ],
         ctx.molphaProgram.programId
       );
@@ -1,7 +1,7 @@
 import * as anchor from "@coral-xyz/anchor";
 import { assert } from "chai";
 import { PublicKey, SystemProgram, Transaction } from "@solana/web3.js";
-import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAccount } from "@solana/spl-token";
 import nacl from "tweetnacl";
 import { BankrunProvider } from "anchor-bankrun";
 import {
@@ -11,6 +11,14 @@ import {
   getDataSourcePda,
   createTestDataSourceInfo,
   createFeedParams,
+  createTestFeed,
+  createAnswerReport,
+  encodeValue,
+  fetchFeedHistory,
+  getFeedHistoryPda,
+  publishTestAnswer,
+  reportSigningMessage,
+  transmitterAccounts,
 } from "../setup";
 
 async function safePastOnchainTimestamp(
@@ -85,8 +93,10 @@ describe("Publish Answer Instruction", () => {
     const publicFeedParams = {
       jobId: Array.from(Buffer.from(publicFeedId.padEnd(32, "\0"))),
       feedType: { public: {} },
+      valueType: { i128Price: {} },
       minSignaturesThreshold: 2,
       frequency: new anchor.BN(300),
+      updatePolicy: { minUpdateInterval: new anchor.BN(0), deviationThresholdBps: 0, heartbeat: new anchor.BN(0), maxDeviationBps: 0, outlierSignaturesThreshold: 0, emaPeriod: new anchor.BN(0) },
       ipfsCid: "QmTestPublic",
       name: publicFeedId,
     };
@@ -96,10 +106,6 @@ describe("Publish Answer Instruction", () => {
         Buffer.from("feed"),
         ctx.authority.publicKey.toBuffer(),
         Buffer.from(publicFeedParams.name),
-        Buffer.from([0]), // FeedType::Public = 0
-        Buffer.from([publicFeedParams.minSignaturesThreshold]),
-        publicFeedParams.frequency.toBuffer("le", 8),
-        Buffer.from(publicFeedParams.jobId),
       ],
       ctx.molphaProgram.programId
     );
@@ -132,8 +138,10 @@ describe("Publish Answer Instruction", () => {
     const personalFeedParams = {
       jobId: Array.from(Buffer.from(personalFeedId.padEnd(32, "\0"))),
       feedType: { personal: {} },
+      valueType: { i128Price: {} },
       minSignaturesThreshold: 2,
       frequency: new anchor.BN(300),
+      updatePolicy: { minUpdateInterval: new anchor.BN(0), deviationThresholdBps: 0, heartbeat: new anchor.BN(0), maxDeviationBps: 0, outlierSignaturesThreshold: 0, emaPeriod: new anchor.BN(0) },
       ipfsCid: "QmTestPersonal",
       name: personalFeedId,
     };
@@ -143,10 +151,6 @@ describe("Publish Answer Instruction", () => {
         Buffer.from("feed"),
         ctx.authority.publicKey.toBuffer(),
         Buffer.from(personalFeedParams.name),
-        Buffer.from([1]), // FeedType::Personal = 1
-        Buffer.from([personalFeedParams.minSignaturesThreshold]),
-        personalFeedParams.frequency.toBuffer("le", 8),
-        Buffer.from(personalFeedParams.jobId),
       ],
       ctx.molphaProgram.programId
     );
@@ -199,21 +203,13 @@ describe("Publish Answer Instruction", () => {
 
   describe("Basic Publishing", () => {
     it("Successfully publishes answer to public feed with valid signatures", async () => {
-      const answer = {
-        value: Array.from(
-          Buffer.from(
-            "1000000000000000000000000000000000000000000000000000000000000000",
-            "hex"
-          )
-        ), // 32 bytes
-        timestamp: await safePastOnchainTimestamp(
-          ctx.molphaProgram.provider,
-          100
-        ),
-      };
+      const answer = createAnswerReport(
+        1000,
+        await safePastOnchainTimestamp(ctx.molphaProgram.provider, 100)
+      );
 
-      // The message must be the answer value for signature verification
-      const message = Buffer.from(answer.value);
+      // The message must be the serialized report for signature verification
+      const message = reportSigningMessage(publicFeedPDA, answer);
 
       const signers = [ctx.nodes[0], ctx.nodes[1]]; // Use 2 signers to meet threshold
 
@@ -234,6 +230,7 @@ describe("Publish Answer Instruction", () => {
           feed: publicFeedPDA,
           nodeRegistry: ctx.nodeRegistryPDA,
           instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          ...transmitterAccounts(ctx),
         })
         .preInstructions(preIxs)
         .rpc();
@@ -241,29 +238,20 @@ describe("Publish Answer Instruction", () => {
       // Verify the answer was published
       const feed = await ctx.molphaProgram.account.feed.fetch(publicFeedPDA);
       assert.deepEqual(feed.latestAnswer.value, answer.value);
-      assert.ok(feed.latestAnswer.timestamp.eq(answer.timestamp));
+      assert.ok(feed.latestAnswer.observedTo.eq(answer.observedTo));
 
-      // Verify answer history was updated
-      assert.equal(feed.answerHistory.length, 1);
-      assert.deepEqual(feed.answerHistory[0].value, answer.value);
+      // The feed keeps no history account by default
+      assert.ok(feed.history.equals(PublicKey.default));
     });
 
     it("Successfully publishes answer to personal feed with subscription", async () => {
-      const answer = {
-        value: Array.from(
-          Buffer.from(
-            "2000000000000000000000000000000000000000000000000000000000000000",
-            "hex"
-          )
-        ), // 32 bytes
-        timestamp: await safePastOnchainTimestamp(
-          ctx.molphaProgram.provider,
-          90
-        ), // 5 seconds ago
-      };
+      const answer = createAnswerReport(
+        2000,
+        await safePastOnchainTimestamp(ctx.molphaProgram.provider, 90)
+      );
 
-      // The message must be the answer value for signature verification
-      const message = Buffer.from(answer.value);
+      // The message must be the serialized report for signature verification
+      const message = reportSigningMessage(personalFeedPDA, answer);
 
       // Get initial feed balance
       const initialFeed = await ctx.molphaProgram.account.feed.fetch(
@@ -290,6 +278,7 @@ describe("Publish Answer Instruction", () => {
           feed: personalFeedPDA,
           nodeRegistry: ctx.nodeRegistryPDA,
           instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          ...transmitterAccounts(ctx),
         })
         .preInstructions(preIxs)
         .rpc();
@@ -297,27 +286,19 @@ describe("Publish Answer Instruction", () => {
       // Verify the answer was published
       const feed = await ctx.molphaProgram.account.feed.fetch(personalFeedPDA);
       assert.deepEqual(feed.latestAnswer.value, answer.value);
-      assert.ok(feed.latestAnswer.timestamp.eq(answer.timestamp));
+      assert.ok(feed.latestAnswer.observedTo.eq(answer.observedTo));
     });
   });
 
   describe("Signature Validation", () => {
     it("Fails with insufficient signatures", async () => {
-      const answer = {
-        value: Array.from(
-          Buffer.from(
-            "3000000000000000000000000000000000000000000000000000000000000000",
-            "hex"
-          )
-        ),
-        timestamp: await safePastOnchainTimestamp(
-          ctx.molphaProgram.provider,
-          80
-        ), // 3 seconds ago
-      };
+      const answer = createAnswerReport(
+        3000,
+        await safePastOnchainTimestamp(ctx.molphaProgram.provider, 80)
+      );
 
-      // The message must be the answer value for signature verification
-      const message = Buffer.from(answer.value);
+      // The message must be the serialized report for signature verification
+      const message = reportSigningMessage(personalFeedPDA, answer);
 
       const signers = [ctx.nodes[0]];
       const preIxs = signers.map((signer) => {
@@ -336,6 +317,7 @@ describe("Publish Answer Instruction", () => {
             feed: personalFeedPDA,
             nodeRegistry: ctx.nodeRegistryPDA,
             instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            ...transmitterAccounts(ctx),
           })
           .preInstructions(preIxs)
           .rpc();
@@ -345,10 +327,10 @@ describe("Publish Answer Instruction", () => {
           personalFeedPDA
         );
         console.log(
-          "feed.latestAnswer.timestamp:",
-          feed.latestAnswer.timestamp.toNumber()
+          "feed.latestAnswer.observedTo:",
+          feed.latestAnswer.observedTo.toNumber()
         );
-        console.log("answer.timestamp:", answer.timestamp.toNumber());
+        console.log("answer.observedTo:", answer.observedTo.toNumber());
         console.log("myerror:", JSON.stringify(error));
         assert.ok(
           error.message.includes("NotEnoughSignatures") ||
@@ -358,21 +340,13 @@ describe("Publish Answer Instruction", () => {
     });
 
     it("Fails with signatures from non-registered nodes", async () => {
-      const answer = {
-        value: Array.from(
-          Buffer.from(
-            "4000000000000000000000000000000000000000000000000000000000000000",
-            "hex"
-          )
-        ),
-        timestamp: await safePastOnchainTimestamp(
-          ctx.molphaProgram.provider,
-          70
-        ), // 2 seconds ago
-      };
+      const answer = createAnswerReport(
+        4000,
+        await safePastOnchainTimestamp(ctx.molphaProgram.provider, 70)
+      );
 
-      // The message must be the answer value for signature verification
-      const message = Buffer.from(answer.value);
+      // The message must be the serialized report for signature verification
+      const message = reportSigningMessage(personalFeedPDA, answer);
 
       const transaction = new Transaction();
 
@@ -400,6 +374,7 @@ describe("Publish Answer Instruction", () => {
             feed: personalFeedPDA,
             nodeRegistry: ctx.nodeRegistryPDA,
             instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            ...transmitterAccounts(ctx),
           })
           .preInstructions(preIxs)
           .rpc();
@@ -414,20 +389,12 @@ describe("Publish Answer Instruction", () => {
     });
 
     it("Fails with wrong message in signatures", async () => {
-      const answer = {
-        value: Array.from(
-          Buffer.from(
-            "5000000000000000000000000000000000000000000000000000000000000000",
-            "hex"
-          )
-        ),
-        timestamp: await safePastOnchainTimestamp(
-          ctx.molphaProgram.provider,
-          60
-        ), // 1 second ago
-      };
+      const answer = createAnswerReport(
+        5000,
+        await safePastOnchainTimestamp(ctx.molphaProgram.provider, 60)
+      );
 
-      // Sign a different message (not the answer value)
+      // Sign a different message (not the serialized report)
       const wrongMessage = Buffer.from("wrong-message-not-answer-value");
 
       const signers = [ctx.nodes[0], ctx.nodes[1]];
@@ -449,6 +416,7 @@ describe("Publish Answer Instruction", () => {
             feed: publicFeedPDA,
             nodeRegistry: ctx.nodeRegistryPDA,
             instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            ...transmitterAccounts(ctx),
           })
           .preInstructions(preIxs)
           .rpc();
@@ -460,6 +428,41 @@ describe("Publish Answer Instruction", () => {
         );
       }
     });
+
+    it("Fails to replay a report signed for another feed", async () => {
+      const answer = createAnswerReport(
+        5000,
+        await safePastOnchainTimestamp(ctx.molphaProgram.provider, 60)
+      );
+      const message = reportSigningMessage(personalFeedPDA, answer);
+
+      const preIxs = [ctx.nodes[0], ctx.nodes[1]].map((signer) =>
+        anchor.web3.Ed25519Program.createInstructionWithPublicKey({
+          publicKey: signer.publicKey.toBytes(),
+          message,
+          signature: nacl.sign.detached(message, signer.secretKey),
+        })
+      );
+
+      try {
+        await ctx.molphaProgram.methods
+          .publishAnswer(answer)
+          .accountsPartial({
+            feed: publicFeedPDA,
+            nodeRegistry: ctx.nodeRegistryPDA,
+            instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            ...transmitterAccounts(ctx),
+          })
+          .preInstructions(preIxs)
+          .rpc();
+        assert.fail("Should have failed with a report signed for another feed");
+      } catch (error: any) {
+        assert.ok(
+          error.message.includes("NotEnoughSignatures") ||
+            error.message.includes("custom program error")
+        );
+      }
+    });
   });
 
   describe("Timestamp Validation", () => {
@@ -470,19 +473,11 @@ describe("Publish Answer Instruction", () => {
       const currentFeed = await ctx.molphaProgram.account.feed.fetch(
         publicFeedPDA
       );
-      const pastTimestamp = currentFeed.latestAnswer.timestamp.sub(
+      const pastTimestamp = currentFeed.latestAnswer.observedTo.sub(
         new anchor.BN(1)
       );
 
-      const answer = {
-        value: Array.from(
-          Buffer.from(
-            "6000000000000000000000000000000000000000000000000000000000000000",
-            "hex"
-          )
-        ),
-        timestamp: pastTimestamp,
-      };
+      const answer = createAnswerReport(6000, pastTimestamp);
 
       const transaction = new Transaction();
       const signers = [ctx.nodes[0], ctx.nodes[1]];
@@ -505,6 +500,7 @@ describe("Publish Answer Instruction", () => {
             nodeRegistry: ctx.nodeRegistryPDA,
             protocolConfig: ctx.protocolConfigPDA,
             instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            ...transmitterAccounts(ctx),
           })
           .instruction()
       );
@@ -526,15 +522,7 @@ describe("Publish Answer Instruction", () => {
         Math.floor(Date.now() / 1000) + 3600
       ); // 1 hour in future
 
-      const answer = {
-        value: Array.from(
-          Buffer.from(
-            "7000000000000000000000000000000000000000000000000000000000000000",
-            "hex"
-          )
-        ),
-        timestamp: futureTimestamp,
-      };
+      const answer = createAnswerReport(7000, futureTimestamp);
 
       const transaction = new Transaction();
       const signers = [ctx.nodes[0], ctx.nodes[1]];
@@ -557,6 +545,7 @@ describe("Publish Answer Instruction", () => {
             nodeRegistry: ctx.nodeRegistryPDA,
             protocolConfig: ctx.protocolConfigPDA,
             instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            ...transmitterAccounts(ctx),
           })
           .instruction()
       );
@@ -581,8 +570,10 @@ describe("Publish Answer Instruction", () => {
       const historyFeedParams = {
         jobId: Array.from(Buffer.from(historyFeedId.padEnd(32, "\0"))),
         feedType: { public: {} },
+        valueType: { i128Price: {} },
         minSignaturesThreshold: 2,
         frequency: new anchor.BN(300),
+        updatePolicy: { minUpdateInterval: new anchor.BN(0), deviationThresholdBps: 0, heartbeat: new anchor.BN(0), maxDeviationBps: 0, outlierSignaturesThreshold: 0, emaPeriod: new anchor.BN(0) },
         ipfsCid: "QmHistoryTest",
         name: historyFeedName,
       };
@@ -592,13 +583,10 @@ describe("Publish Answer Instruction", () => {
           Buffer.from("feed"),
           ctx.authority.publicKey.toBuffer(),
           Buffer.from(historyFeedParams.name),
-          Buffer.from([0]), // FeedType::Public.to_seed() = [0]
-          Buffer.from([historyFeedParams.minSignaturesThreshold]), // u8.to_le_bytes() = [value]
-          historyFeedParams.frequency.toBuffer("le", 8),
-          Buffer.from(historyFeedParams.jobId),
         ],
         ctx.molphaProgram.programId
       );
+      const [historyPDA] = getFeedHistoryPda(ctx.molphaProgram.programId, historyFeedPDA);
 
       const dataSourceInfo = createTestDataSourceInfo(
         0,
@@ -641,6 +629,15 @@ describe("Publish Answer Instruction", () => {
         })
         .rpc();
 
+      await ctx.molphaProgram.methods
+        .createFeedHistory(10)
+        .accountsPartial({
+          feed: historyFeedPDA,
+          history: historyPDA,
+          authority: ctx.authority.publicKey,
+        })
+        .rpc();
+
       // Top up the history feed with sufficient balance
       await ctx.molphaProgram.methods
         .topUp(new anchor.BN(100000)) // 100,000 lamports
@@ -653,15 +650,13 @@ describe("Publish Answer Instruction", () => {
       // Publish multiple answers to test history
       const numAnswers = 5;
       for (let i = 0; i < numAnswers; i++) {
-        const answer = {
-          value: Array.from(
-            Buffer.from(`${i.toString().padStart(64, "0")}`, "hex")
-          ),
-          timestamp: new anchor.BN(Math.floor(Date.now() / 1000) - 100 + i), // Start from 100 seconds ago
-        };
+        const answer = createAnswerReport(
+          i,
+          new anchor.BN(Math.floor(Date.now() / 1000) - 100 + i) // Start from 100 seconds ago
+        );
 
-        // The message must be the answer value for signature verification
-        const message = Buffer.from(answer.value);
+        // The message must be the serialized report for signature verification
+        const message = reportSigningMessage(historyFeedPDA, answer);
 
         // Create signature instructions like the working tests
         const preIxs: anchor.web3.TransactionInstruction[] = [];
@@ -682,8 +677,10 @@ describe("Publish Answer Instruction", () => {
           .publishAnswer(answer)
           .accountsPartial({
             feed: historyFeedPDA,
+            history: historyPDA,
             nodeRegistry: ctx.nodeRegistryPDA,
             instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            ...transmitterAccounts(ctx),
           })
           .preInstructions(preIxs)
           .rpc();
@@ -694,21 +691,377 @@ describe("Publish Answer Instruction", () => {
 
       // Verify history was maintained
       const feed = await ctx.molphaProgram.account.feed.fetch(historyFeedPDA);
-      assert.equal(feed.answerHistory.length, numAnswers);
+      const history = await fetchFeedHistory(ctx, historyPDA);
+      assert.equal(history.len, numAnswers);
 
       // Verify latest answer is correct
-      const expectedLatestValue = Array.from(
-        Buffer.from(`${(numAnswers - 1).toString().padStart(64, "0")}`, "hex")
-      );
-      assert.deepEqual(feed.latestAnswer.value, expectedLatestValue);
+      assert.deepEqual(feed.latestAnswer.value, encodeValue(numAnswers - 1));
+      assert.equal(feed.latestAnswer.roundId.toNumber(), numAnswers);
 
       // Verify history contains all answers in order
       for (let i = 0; i < numAnswers; i++) {
-        const expectedValue = Array.from(
-          Buffer.from(`${i.toString().padStart(64, "0")}`, "hex")
+        assert.deepEqual(history.entries[i].value, encodeValue(i));
+        assert.equal(history.entries[i].roundId.toNumber(), i + 1);
+      }
+    });
+  });
+
+  describe("Update Policy", () => {
+    let policyFeedPDA: PublicKey;
+    let baseTimestamp: number;
+
+    async function publish(value: number, timestamp: number) {
+      const answer = createAnswerReport(value, timestamp);
+      const message = reportSigningMessage(policyFeedPDA, answer);
+      const preIxs = [ctx.nodes[0], ctx.nodes[1]].map((signer) =>
+        anchor.web3.Ed25519Program.createInstructionWithPublicKey({
+          publicKey: signer.publicKey.toBytes(),
+          message,
+          signature: nacl.sign.detached(message, signer.secretKey),
+        })
+      );
+      await ctx.molphaProgram.methods
+        .publishAnswer(answer)
+        .accountsPartial({
+          feed: policyFeedPDA,
+          nodeRegistry: ctx.nodeRegistryPDA,
+          instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          ...transmitterAccounts(ctx),
+        })
+        .preInstructions(preIxs)
+        .rpc();
+    }
+
+    before(async () => {
+      const params = {
+        ...createFeedParams("policy-feed-test", { public: {} }),
+        updatePolicy: {
+          minUpdateInterval: new anchor.BN(60),
+          deviationThresholdBps: 100, // 1%
+          heartbeat: new anchor.BN(3600),
+          maxDeviationBps: 0,
+          outlierSignaturesThreshold: 0,
+          emaPeriod: new anchor.BN(0),
+        },
+      };
+      policyFeedPDA = await createTestFeed(ctx, params);
+
+      baseTimestamp = (
+        await safePastOnchainTimestamp(ctx.molphaProgram.provider, 1000)
+      ).toNumber();
+      await publish(10_000, baseTimestamp);
+    });
+
+    it("Rejects answers published before the minimum interval", async () => {
+      try {
+        await publish(20_000, baseTimestamp + 30);
+        assert.fail("Should have failed with UpdateTooFrequent");
+      } catch (error: any) {
+        assert.ok(
+          error.message.includes("UpdateTooFrequent") ||
+            error.message.includes("custom program error")
+        );
+      }
+    });
+
+    it("Rejects answers below the deviation threshold before the heartbeat", async () => {
+      try {
+        await publish(10_050, baseTimestamp + 120); // 0.5%
+        assert.fail("Should have failed with UpdateConditionsNotMet");
+      } catch (error: any) {
+        assert.ok(
+          error.message.includes("UpdateConditionsNotMet") ||
+            error.message.includes("custom program error")
         );
-        assert.deepEqual(feed.answerHistory[i].value, expectedValue);
       }
     });
+
+    it("Accepts answers that meet the deviation threshold", async () => {
+      await publish(10_200, baseTimestamp + 180); // 2%
+
+      const feed = await ctx.molphaProgram.account.feed.fetch(policyFeedPDA);
+      assert.ok(feed.latestAnswer.observedTo.eqn(baseTimestamp + 180));
+    });
+  });
+
+  describe("Value Types", () => {
+    let boolFeedPDA: PublicKey;
+
+    async function publishBool(value: number, timestamp: number) {
+      const answer = createAnswerReport(value, timestamp);
+      const message = reportSigningMessage(boolFeedPDA, answer);
+      const preIxs = [ctx.nodes[0], ctx.nodes[1]].map((signer) =>
+        anchor.web3.Ed25519Program.createInstructionWithPublicKey({
+          publicKey: signer.publicKey.toBytes(),
+          message,
+          signature: nacl.sign.detached(message, signer.secretKey),
+        })
+      );
+      await ctx.molphaProgram.methods
+        .publishAnswer(answer)
+        .accountsPartial({
+          feed: boolFeedPDA,
+          nodeRegistry: ctx.nodeRegistryPDA,
+          instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          ...transmitterAccounts(ctx),
+        })
+        .preInstructions(preIxs)
+        .rpc();
+    }
+
+    before(async () => {
+      boolFeedPDA = await createTestFeed(ctx, {
+        ...createFeedParams("bool-feed-test", { public: {} }),
+        valueType: { bool: {} },
+      });
+    });
+
+    it("Rejects values outside the feed's value type", async () => {
+      const timestamp = await safePastOnchainTimestamp(ctx.molphaProgram.provider, 100);
+      try {
+        await publishBool(2, timestamp.toNumber());
+        assert.fail("Should have failed with InvalidValue");
+      } catch (error: any) {
+        assert.ok(
+          error.message.includes("InvalidValue") ||
+            error.message.includes("custom program error")
+        );
+      }
+    });
+
+    it("Accepts values matching the feed's value type", async () => {
+      const timestamp = await safePastOnchainTimestamp(ctx.molphaProgram.provider, 100);
+      await publishBool(1, timestamp.toNumber());
+
+      const feed = await ctx.molphaProgram.account.feed.fetch(boolFeedPDA);
+      assert.equal(feed.valueType, 2); // ValueType::Bool
+      assert.deepEqual(feed.latestAnswer.value, encodeValue(1));
+    });
+  });
+
+  describe("Outlier Guard", () => {
+    let guardedFeedPDA: PublicKey;
+    let baseTimestamp: number;
+
+    before(async () => {
+      guardedFeedPDA = await createTestFeed(ctx, {
+        ...createFeedParams("outlier-feed-test", { public: {} }),
+        updatePolicy: {
+          minUpdateInterval: new anchor.BN(0),
+          deviationThresholdBps: 0,
+          heartbeat: new anchor.BN(0),
+          maxDeviationBps: 1000, // 10%
+          outlierSignaturesThreshold: 3,
+          emaPeriod: new anchor.BN(0),
+        },
+      });
+
+      baseTimestamp = (
+        await safePastOnchainTimestamp(ctx.molphaProgram.provider, 1000)
+      ).toNumber();
+      await publishTestAnswer(ctx, guardedFeedPDA, createAnswerReport(10_000, baseTimestamp));
+    });
+
+    it("Accepts answers within the maximum deviation", async () => {
+      await publishTestAnswer(ctx, guardedFeedPDA, createAnswerReport(10_500, baseTimestamp + 10));
+
+      const feed = await ctx.molphaProgram.account.feed.fetch(guardedFeedPDA);
+      assert.deepEqual(feed.latestAnswer.value, encodeValue(10_500));
+    });
+
+    it("Drops outliers signed by the minimum quorum only", async () => {
+      await publishTestAnswer(ctx, guardedFeedPDA, createAnswerReport(20_000, baseTimestamp + 20));
+
+      const feed = await ctx.molphaProgram.account.feed.fetch(guardedFeedPDA);
+      assert.deepEqual(feed.latestAnswer.value, encodeValue(10_500));
+    });
+
+    it("Accepts outliers signed by the elevated quorum", async () => {
+      await publishTestAnswer(
+        ctx,
+        guardedFeedPDA,
+        createAnswerReport(20_000, baseTimestamp + 30),
+        ctx.nodes.slice(0, 3)
+      );
+
+      const feed = await ctx.molphaProgram.account.feed.fetch(guardedFeedPDA);
+      assert.deepEqual(feed.latestAnswer.value, encodeValue(20_000));
+      assert.equal(feed.latestAnswer.signerCount, 3);
+    });
+  });
+
+  describe("Priority Fee Metering", () => {
+    let meteredFeedPDA: PublicKey;
+
+    async function setFeeCoverage(
+      maxPriorityFeeCoverage: number,
+      smoothingWindow: number,
+      transmitterWindow = 0,
+      transmitterFallbackDelay = 0
+    ) {
+      await ctx.molphaProgram.methods
+        .updateProtocolConfig({
+          basePricePerSecondScaled: new anchor.BN(0),
+          frequencyCoefficient: new anchor.BN(0),
+          signersCoefficient: new anchor.BN(0),
+          rewardPercentage: new anchor.BN(0),
+          priorityFeeBufferPercentage: 0,
+          maxPriorityFeeCoverage: new anchor.BN(maxPriorityFeeCoverage),
+          priorityFeeSmoothingWindow: smoothingWindow,
+          transmitterWindow: new anchor.BN(transmitterWindow),
+          transmitterFallbackDelay: new anchor.BN(transmitterFallbackDelay),
+          crankTipBps: 0,
+          gracePeriod: new anchor.BN(0),
+          lowSubscriptionThreshold: new anchor.BN(0),
+          lowPriorityBudgetThreshold: new anchor.BN(0),
+        })
+        .accountsPartial({
+          protocolConfig: ctx.protocolConfigPDA,
+          authority: ctx.authority.publicKey,
+        })
+        .rpc();
+    }
+
+    before(async () => {
+      meteredFeedPDA = await createTestFeed(
+        ctx,
+        createFeedParams("metered-feed-test", { public: {} }),
+        86400,
+        1_000_000
+      );
+      await setFeeCoverage(1_000_000, 1);
+    });
+
+    after(async () => {
+      await setFeeCoverage(0, 0);
+    });
+
+    it("Charges the metered fees and reimburses the transmitter", async () => {
+      const transmitterBefore = await getAccount(ctx.provider.connection, ctx.userTokenAccount);
+      const timestamp = await safePastOnchainTimestamp(ctx.molphaProgram.provider, 10);
+      const answer = createAnswerReport(7_000, timestamp.toNumber());
+      const message = reportSigningMessage(meteredFeedPDA, answer);
+      const signatureIxs = [ctx.nodes[0], ctx.nodes[1]].map((signer) =>
+        anchor.web3.Ed25519Program.createInstructionWithPublicKey({
+          publicKey: signer.publicKey.toBytes(),
+          message,
+          signature: nacl.sign.detached(message, signer.secretKey),
+        })
+      );
+
+      await ctx.molphaProgram.methods
+        .publishAnswer(answer)
+        .accountsPartial({
+          feed: meteredFeedPDA,
+          nodeRegistry: ctx.nodeRegistryPDA,
+          protocolConfig: ctx.protocolConfigPDA,
+          instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          ...transmitterAccounts(ctx),
+        })
+        .preInstructions([
+          anchor.web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 100_000 }),
+          anchor.web3.ComputeBudgetProgram.setComputeUnitPrice({ microLamports: 1_000 }),
+          ...signatureIxs,
+        ])
+        .rpc();
+
+      // 1_000 micro-lamports * 100_000 CU = 100 lamports, plus 5_000 for the
+      // transaction signature and 2 * 5_000 for the Ed25519 signatures
+      const feed = await ctx.molphaProgram.account.feed.fetch(meteredFeedPDA);
+      assert.equal(feed.consumedPriorityFees.toNumber(), 15_100);
+      assert.equal(feed.averagePriorityFee.toNumber(), 15_100);
+
+      const transmitterAfter = await getAccount(ctx.provider.connection, ctx.userTokenAccount);
+      assert.equal(Number(transmitterAfter.amount - transmitterBefore.amount), 15_100);
+    });
+
+    it("Caps the charged fee at the protocol coverage", async () => {
+      await setFeeCoverage(5_000, 1);
+      const timestamp = await safePastOnchainTimestamp(ctx.molphaProgram.provider, 5);
+      await publishTestAnswer(ctx, meteredFeedPDA, createAnswerReport(7_100, timestamp.toNumber()));
+
+      const feed = await ctx.molphaProgram.account.feed.fetch(meteredFeedPDA);
+      assert.equal(feed.consumedPriorityFees.toNumber(), 15_100 + 5_000);
+    });
+
+    it("Reimburses the metered fee and only smooths the recorded average", async () => {
+      await setFeeCoverage(1_000_000, 4);
+      const before = await ctx.molphaProgram.account.feed.fetch(meteredFeedPDA);
+      const transmitterBefore = await getAccount(ctx.provider.connection, ctx.userTokenAccount);
+      const timestamp = await safePastOnchainTimestamp(ctx.molphaProgram.provider, 4);
+      await publishTestAnswer(ctx, meteredFeedPDA, createAnswerReport(7_050, timestamp.toNumber()));
+
+      // 5_000 for the transaction signature and 2 * 5_000 for the Ed25519 signatures
+      const metered = 15_000;
+      const feed = await ctx.molphaProgram.account.feed.fetch(meteredFeedPDA);
+      const transmitterAfter = await getAccount(ctx.provider.connection, ctx.userTokenAccount);
+      assert.equal(
+        feed.consumedPriorityFees.sub(before.consumedPriorityFees).toNumber(),
+        metered
+      );
+      assert.equal(Number(transmitterAfter.amount - transmitterBefore.amount), metered);
+
+      const samples = Math.min(before.priorityFeeSamples + 1, 4);
+      const average = before.averagePriorityFee.toNumber();
+      assert.equal(
+        feed.averagePriorityFee.toNumber(),
+        average + Math.trunc((metered - average) / samples)
+      );
+    });
+
+    it("Bounds the charged fee by the remaining priority fee budget", async () => {
+      const smallBudgetFeed = await createTestFeed(
+        ctx,
+        createFeedParams("small-budget-feed-test", { public: {} }),
+        86400,
+        20_000
+      );
+      await setFeeCoverage(1_000_000, 1);
+      const first = await safePastOnchainTimestamp(ctx.molphaProgram.provider, 4);
+      await publishTestAnswer(ctx, smallBudgetFeed, createAnswerReport(7_000, first.toNumber()));
+
+      const transmitterBefore = await getAccount(ctx.provider.connection, ctx.userTokenAccount);
+      await publishTestAnswer(ctx, smallBudgetFeed, createAnswerReport(7_001, first.toNumber() + 1));
+      const transmitterAfter = await getAccount(ctx.provider.connection, ctx.userTokenAccount);
+
+      const feed = await ctx.molphaProgram.account.feed.fetch(smallBudgetFeed);
+      assert.equal(feed.consumedPriorityFees.toNumber(), 20_000);
+      assert.equal(Number(transmitterAfter.amount - transmitterBefore.amount), 5_000);
+    });
+
+    describe("Transmitter Schedule", () => {
+      // The provider wallet is not a committee member, so it is never scheduled
+      const WINDOW = 1_000_000_000;
+
+      it("Rejects unscheduled transmitters during the exclusive window", async () => {
+        await setFeeCoverage(1_000_000, 1, WINDOW, WINDOW - 1);
+        const timestamp = await safePastOnchainTimestamp(ctx.molphaProgram.provider, 3);
+        try {
+          await publishTestAnswer(ctx, meteredFeedPDA, createAnswerReport(7_200, timestamp.toNumber()));
+          assert.fail("Should have failed with NotDesignatedTransmitter");
+        } catch (error: any) {
+          assert.ok(
+            error.message.includes("NotDesignatedTransmitter") ||
+              error.message.includes("custom program error")
+          );
+        }
+      });
+
+      it("Accepts anyone after the fallback delay without reimbursing them", async () => {
+        await setFeeCoverage(1_000_000, 1, WINDOW, 0);
+        const before = await ctx.molphaProgram.account.feed.fetch(meteredFeedPDA);
+        const transmitterBefore = await getAccount(ctx.provider.connection, ctx.userTokenAccount);
+
+        const timestamp = await safePastOnchainTimestamp(ctx.molphaProgram.provider, 2);
+        await publishTestAnswer(ctx, meteredFeedPDA, createAnswerReport(7_300, timestamp.toNumber()));
+
+        const feed = await ctx.molphaProgram.account.feed.fetch(meteredFeedPDA);
+        assert.deepEqual(feed.latestAnswer.value, encodeValue(7_300));
+        assert.ok(feed.consumedPriorityFees.eq(before.consumedPriorityFees));
+
+        const transmitterAfter = await getAccount(ctx.provider.connection, ctx.userTokenAccount);
+        assert.equal(transmitterAfter.amount, transmitterBefore.amount);
+      });
+    });
   });
 });
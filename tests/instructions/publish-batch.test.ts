@@ -0,0 +1,126 @@
+import * as anchor from "@coral-xyz/anchor";
+import { assert } from "chai";
+import { PublicKey } from "@solana/web3.js";
+import nacl from "tweetnacl";
+import {
+  setupTestContext,
+  initializeProtocol,
+  TestContext,
+  createFeedParams,
+  createTestFeed,
+  addTestNodes,
+  createAnswerReport,
+  encodeValue,
+  leafHash,
+  buildMerkleTree,
+  AnswerReport,
+  transmitterAccounts,
+} from "../setup";
+
+describe("Publish Batch Instruction", () => {
+  let ctx: TestContext;
+  let feeds: PublicKey[];
+
+  async function publishBatch(
+    root: Buffer,
+    leaves: { report: AnswerReport; proof: Buffer[] }[],
+    feedAccounts: PublicKey[]
+  ) {
+    const preIxs = [ctx.nodes[0], ctx.nodes[1]].map((signer) =>
+      anchor.web3.Ed25519Program.createInstructionWithPublicKey({
+        publicKey: signer.publicKey.toBytes(),
+        message: root,
+        signature: nacl.sign.detached(root, signer.secretKey),
+      })
+    );
+    await ctx.molphaProgram.methods
+      .publishBatch(
+        Array.from(root),
+        leaves.map(({ report, proof }) => ({
+          report,
+          proof: proof.map((hash) => Array.from(hash)),
+        }))
+      )
+      .accountsPartial({
+        nodeRegistry: ctx.nodeRegistryPDA,
+        instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        ...transmitterAccounts(ctx),
+      })
+      .remainingAccounts(
+        feedAccounts.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true }))
+      )
+      .preInstructions(preIxs)
+      .rpc();
+  }
+
+  async function observedTo(secondsInPast: number) {
+    const clock = await ctx.provider.context.banksClient.getClock();
+    return Number(clock.unixTimestamp) - secondsInPast;
+  }
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeProtocol(ctx);
+    await addTestNodes(ctx, 2);
+
+    feeds = [];
+    for (const name of ["batch-feed-a", "batch-feed-b", "batch-feed-c"]) {
+      feeds.push(await createTestFeed(ctx, createFeedParams(name, { public: {} })));
+    }
+  });
+
+  it("Publishes answers to every feed in the batch", async () => {
+    const timestamp = await observedTo(10);
+    const reports = feeds.map((_, i) => createAnswerReport(1_000 + i, timestamp, -2));
+    const { root, proofs } = buildMerkleTree(
+      feeds.map((feed, i) => leafHash(feed, reports[i]))
+    );
+
+    await publishBatch(
+      root,
+      reports.map((report, i) => ({ report, proof: proofs[i] })),
+      feeds
+    );
+
+    for (let i = 0; i < feeds.length; i++) {
+      const feed = await ctx.molphaProgram.account.feed.fetch(feeds[i]);
+      assert.deepEqual(feed.latestAnswer.value, encodeValue(1_000 + i));
+      assert.equal(feed.latestAnswer.signerCount, 2);
+    }
+  });
+
+  it("Rejects a leaf applied to a different feed", async () => {
+    const timestamp = await observedTo(5);
+    const reports = feeds.map((_, i) => createAnswerReport(2_000 + i, timestamp, -2));
+    const { root, proofs } = buildMerkleTree(
+      feeds.map((feed, i) => leafHash(feed, reports[i]))
+    );
+
+    try {
+      // Leaf for feed A passed with feed B's account
+      await publishBatch(root, [{ report: reports[0], proof: proofs[0] }], [feeds[1]]);
+      assert.fail("Should have failed with an invalid proof");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("InvalidMerkleProof") ||
+          error.message.includes("custom program error")
+      );
+    }
+  });
+
+  it("Rejects batches whose leaves and accounts differ in length", async () => {
+    const timestamp = await observedTo(5);
+    const report = createAnswerReport(3_000, timestamp, -2);
+    const { root, proofs } = buildMerkleTree([leafHash(feeds[0], report)]);
+
+    try {
+      await publishBatch(root, [{ report, proof: proofs[0] }], feeds.slice(0, 2));
+      assert.fail("Should have failed with mismatched accounts");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("BatchAccountsMismatch") ||
+          error.message.includes("custom program error")
+      );
+    }
+  });
+});
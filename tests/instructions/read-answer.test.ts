@@ -0,0 +1,118 @@
+import { assert } from "chai";
+import { PublicKey } from "@solana/web3.js";
+import { BN } from "@coral-xyz/anchor";
+import {
+  setupTestContext,
+  initializeProtocol,
+  TestContext,
+  createFeedParams,
+  createTestFeed,
+  addTestNodes,
+  createAnswerReport,
+  encodeValue,
+  publishTestAnswer,
+  FEED_OFFSETS,
+} from "../setup";
+
+describe("Read Answer Instruction", () => {
+  let ctx: TestContext;
+  let feedPDA: PublicKey;
+
+  function readAnswer(feed: PublicKey, maxAgeSeconds: number, minSigners: number) {
+    return ctx.molphaProgram.methods
+      .readAnswer(new BN(maxAgeSeconds), minSigners)
+      .accountsPartial({ feed })
+      .view();
+  }
+
+  async function expectError(promise: Promise<unknown>, name: string) {
+    try {
+      await promise;
+      assert.fail(`Should have failed with ${name}`);
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes(name) ||
+          error.message.includes("custom program error") ||
+          error.message.includes("Simulation failed")
+      );
+    }
+  }
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeProtocol(ctx);
+    await addTestNodes(ctx, 2);
+
+    feedPDA = await createTestFeed(ctx, createFeedParams("read-feed-test", { public: {} }));
+  });
+
+  it("Fails before the first answer", async () => {
+    await expectError(readAnswer(feedPDA, 3600, 1), "NoAnswer");
+  });
+
+  it("Returns the latest answer within the guards", async () => {
+    const clock = await ctx.provider.context.banksClient.getClock();
+    await publishTestAnswer(
+      ctx,
+      feedPDA,
+      createAnswerReport(5_000, Number(clock.unixTimestamp) - 10, -2)
+    );
+
+    const answer = await readAnswer(feedPDA, 3600, 2);
+    assert.deepEqual(answer.value, encodeValue(5_000));
+    assert.equal(answer.signerCount, 2);
+  });
+
+  it("Exposes the hot fields at stable offsets", async () => {
+    const info = await ctx.provider.connection.getAccountInfo(feedPDA);
+    const data = Buffer.from(info!.data);
+    const feed = await ctx.molphaProgram.account.feed.fetch(feedPDA);
+
+    // AnswerRecord: round_id, observed_from, observed_to, value, ...
+    const answer = FEED_OFFSETS.latestAnswer;
+    assert.equal(Number(data.readBigUInt64LE(answer)), feed.latestAnswer.roundId.toNumber());
+    assert.deepEqual(Array.from(data.subarray(answer + 24, answer + 56)), encodeValue(5_000));
+    assert.equal(data.readInt32LE(answer + 72), -2);
+    assert.equal(Number(data.readBigUInt64LE(FEED_OFFSETS.balance)), feed.balance.toNumber());
+    assert.equal(
+      Number(data.readBigInt64LE(FEED_OFFSETS.subscriptionDueTime)),
+      feed.subscriptionDueTime.toNumber()
+    );
+    assert.equal(Number(data.readBigUInt64LE(FEED_OFFSETS.latestRoundId)), 1);
+    assert.equal(data.readUInt8(FEED_OFFSETS.minSignaturesThreshold), feed.minSignaturesThreshold);
+    assert.equal(data.readUInt8(FEED_OFFSETS.status), 0);
+  });
+
+  it("Fails when the answer is older than the maximum age", async () => {
+    await expectError(readAnswer(feedPDA, 5, 1), "StaleAnswer");
+  });
+
+  it("Fails when the answer has too few signers", async () => {
+    await expectError(readAnswer(feedPDA, 3600, 3), "InsufficientSigners");
+  });
+
+  it("Fails while the feed is paused", async () => {
+    await ctx.molphaProgram.methods
+      .setFeedStatus({ paused: {} })
+      .accountsPartial({
+        feed: feedPDA,
+        authority: ctx.authority.publicKey,
+        protocolConfig: ctx.protocolConfigPDA,
+        successor: null,
+      })
+      .rpc();
+    await expectError(readAnswer(feedPDA, 3600, 1), "FeedPaused");
+
+    await ctx.molphaProgram.methods
+      .setFeedStatus({ active: {} })
+      .accountsPartial({
+        feed: feedPDA,
+        authority: ctx.authority.publicKey,
+        protocolConfig: ctx.protocolConfigPDA,
+        successor: null,
+      })
+      .rpc();
+    const answer = await readAnswer(feedPDA, 3600, 1);
+    assert.deepEqual(answer.value, encodeValue(5_000));
+  });
+});
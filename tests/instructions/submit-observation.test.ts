@@ -0,0 +1,262 @@
+import * as anchor from "@coral-xyz/anchor";
+import { assert } from "chai";
+import { Keypair, PublicKey, SystemProgram, Transaction } from "@solana/web3.js";
+import {
+  setupTestContext,
+  initializeProtocol,
+  TestContext,
+  createFeedParams,
+  createTestFeed,
+  addTestNodes,
+  createAnswerReport,
+  encodeValue,
+} from "../setup";
+import { Clock } from "solana-bankrun";
+
+function getRoundPda(programId: PublicKey, feed: PublicKey, roundId: number) {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("round"),
+      feed.toBuffer(),
+      new anchor.BN(roundId).toBuffer("le", 8),
+    ],
+    programId
+  );
+}
+
+describe("Submit Observation Instruction", () => {
+  let ctx: TestContext;
+  let feedPDA: PublicKey;
+
+  async function submit(
+    node: Keypair,
+    roundId: number,
+    value: anchor.BN | number,
+    exponent = -2,
+    feed = feedPDA
+  ) {
+    const [roundPDA] = getRoundPda(ctx.molphaProgram.programId, feed, roundId);
+    const clock = await ctx.provider.context.banksClient.getClock();
+    const report = createAnswerReport(
+      value,
+      Number(clock.unixTimestamp) - 1,
+      exponent
+    );
+    await ctx.molphaProgram.methods
+      .submitObservation(new anchor.BN(roundId), report)
+      .accountsPartial({
+        feed,
+        round: roundPDA,
+        nodeRegistry: ctx.nodeRegistryPDA,
+        protocolConfig: ctx.protocolConfigPDA,
+        node: node.publicKey,
+      })
+      .signers([node])
+      .rpc();
+    return roundPDA;
+  }
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeProtocol(ctx);
+    await addTestNodes(ctx, 3);
+
+    // Nodes pay for their own submissions and the round rent
+    const fund = new Transaction();
+    for (const node of ctx.nodes.slice(0, 3)) {
+      fund.add(
+        SystemProgram.transfer({
+          fromPubkey: ctx.authority.publicKey,
+          toPubkey: node.publicKey,
+          lamports: 1_000_000_000,
+        })
+      );
+    }
+    await ctx.provider.sendAndConfirm(fund, [ctx.authority.payer]);
+
+    feedPDA = await createTestFeed(
+      ctx,
+      createFeedParams("round-feed-test", { public: {} })
+    );
+  });
+
+  it("Opens a round and finalizes it once the threshold is reached", async () => {
+    const roundPDA = await submit(ctx.nodes[0], 1, 100);
+
+    let round = await ctx.molphaProgram.account.round.fetch(roundPDA);
+    assert.equal(round.roundId.toNumber(), 1);
+    assert.equal(round.submissions.length, 1);
+    assert.isFalse(round.finalized);
+
+    await submit(ctx.nodes[1], 1, 102);
+
+    round = await ctx.molphaProgram.account.round.fetch(roundPDA);
+    assert.isTrue(round.finalized);
+
+    const feed = await ctx.molphaProgram.account.feed.fetch(feedPDA);
+    assert.equal(feed.latestRoundId.toNumber(), 1);
+    assert.deepEqual(feed.latestAnswer.value, encodeValue(102)); // upper median
+    assert.equal(feed.latestAnswer.exponent, -2);
+    assert.equal(feed.latestAnswer.signerCount, 2);
+  });
+
+  it("Rejects submissions to a finalized round", async () => {
+    try {
+      await submit(ctx.nodes[2], 1, 101);
+      assert.fail("Should have failed on a finalized round");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("RoundAlreadyFinalized") ||
+          error.message.includes("custom program error")
+      );
+    }
+  });
+
+  it("Rejects duplicate submissions from the same node", async () => {
+    await submit(ctx.nodes[0], 2, 110);
+    try {
+      await submit(ctx.nodes[0], 2, 111);
+      assert.fail("Should have failed with duplicate submission");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("DuplicateSubmission") ||
+          error.message.includes("custom program error")
+      );
+    }
+  });
+
+  it("Rejects observations with a different exponent", async () => {
+    try {
+      await submit(ctx.nodes[1], 2, 11_000, -4);
+      assert.fail("Should have failed with exponent mismatch");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("ExponentMismatch") ||
+          error.message.includes("custom program error")
+      );
+    }
+  });
+
+  it("Rejects opening a round that skips the current one", async () => {
+    try {
+      await submit(ctx.nodes[1], 4, 120);
+      assert.fail("Should have failed with invalid round id");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("InvalidRoundId") ||
+          error.message.includes("custom program error")
+      );
+    }
+  });
+
+  it("Rejects observations from unregistered nodes", async () => {
+    const outsider = Keypair.generate();
+    try {
+      await submit(outsider, 2, 130);
+      assert.fail("Should have failed with unregistered node");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("NodeNotFound") ||
+          error.message.includes("custom program error") ||
+          error.message.includes("insufficient")
+      );
+    }
+  });
+
+  it("Takes the median of U256 values beyond the i128 range", async () => {
+    const params: any = createFeedParams("round-u256-test", { public: {} });
+    params.valueType = { u256: {} };
+    const u256Feed = await createTestFeed(ctx, params);
+    const large = new anchor.BN(1).shln(200);
+
+    await submit(ctx.nodes[0], 1, large.addn(5), 0, u256Feed);
+    await submit(ctx.nodes[1], 1, large, 0, u256Feed);
+
+    const feed = await ctx.molphaProgram.account.feed.fetch(u256Feed);
+    assert.deepEqual(feed.latestAnswer.value, encodeValue(large.addn(5))); // upper median
+  });
+
+  it("Applies the update policy when a round finalizes", async () => {
+    const params = createFeedParams("round-policy-test", { public: {} });
+    params.updatePolicy.deviationThresholdBps = 1000; // 10%
+    params.updatePolicy.heartbeat = new anchor.BN(3600);
+    const policyFeed = await createTestFeed(ctx, params);
+
+    await submit(ctx.nodes[0], 1, 100, -2, policyFeed);
+    await submit(ctx.nodes[1], 1, 100, -2, policyFeed);
+
+    const clock = await ctx.provider.context.banksClient.getClock();
+    ctx.provider.context.setClock(
+      new Clock(
+        clock.slot,
+        clock.epochStartTimestamp,
+        clock.epoch,
+        clock.leaderScheduleEpoch,
+        clock.unixTimestamp + BigInt(10)
+      )
+    );
+
+    // A 1% move before the heartbeat does not meet the policy
+    await submit(ctx.nodes[0], 2, 101, -2, policyFeed);
+    try {
+      await submit(ctx.nodes[1], 2, 101, -2, policyFeed);
+      assert.fail("Should have failed with UpdateConditionsNotMet");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("UpdateConditionsNotMet") ||
+          error.message.includes("custom program error"),
+        error.message
+      );
+    }
+  });
+
+  it("Keeps the outlier quorum of an open round when nodes are added", async () => {
+    const params = createFeedParams("round-outlier-test", { public: {} });
+    params.updatePolicy.maxDeviationBps = 1000; // 10%, quorum of every node
+    const outlierFeed = await createTestFeed(ctx, params);
+
+    await submit(ctx.nodes[0], 1, 100, -2, outlierFeed);
+    await submit(ctx.nodes[1], 1, 100, -2, outlierFeed);
+
+    const clock = await ctx.provider.context.banksClient.getClock();
+    ctx.provider.context.setClock(
+      new Clock(
+        clock.slot,
+        clock.epochStartTimestamp,
+        clock.epoch,
+        clock.leaderScheduleEpoch,
+        clock.unixTimestamp + BigInt(10)
+      )
+    );
+
+    // The round opens with three registered nodes, then the registry grows
+    const roundPDA = await submit(ctx.nodes[0], 2, 200, -2, outlierFeed);
+    await addTestNodes(ctx, 5);
+    await submit(ctx.nodes[1], 2, 200, -2, outlierFeed);
+    let round = await ctx.molphaProgram.account.round.fetch(roundPDA);
+    assert.equal(round.outlierQuorum, 3);
+    assert.equal(round.maxSubmissions, 3);
+    assert.isFalse(round.finalized);
+
+    await submit(ctx.nodes[2], 2, 200, -2, outlierFeed);
+    round = await ctx.molphaProgram.account.round.fetch(roundPDA);
+    assert.isTrue(round.finalized);
+    const feed = await ctx.molphaProgram.account.feed.fetch(outlierFeed);
+    assert.deepEqual(feed.latestAnswer.value, encodeValue(200));
+  });
+
+  it("Closes a finalized round and refunds the opener", async () => {
+    const [roundPDA] = getRoundPda(ctx.molphaProgram.programId, feedPDA, 1);
+    await ctx.molphaProgram.methods
+      .closeRound()
+      .accountsPartial({
+        feed: feedPDA,
+        round: roundPDA,
+        openedBy: ctx.nodes[0].publicKey,
+      })
+      .rpc();
+
+    const info = await ctx.provider.connection.getAccountInfo(roundPDA);
+    assert.isNull(info);
+  });
+});
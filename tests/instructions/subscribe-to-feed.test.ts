@@ -0,0 +1,173 @@
+import { assert } from "chai";
+import { PublicKey } from "@solana/web3.js";
+import { BN } from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAccount } from "@solana/spl-token";
+import {
+  setupTestContext,
+  initializeProtocol,
+  TestContext,
+  createFeedParams,
+  createTestFeed,
+  addTestNodes,
+  createAnswerReport,
+  publishTestAnswer,
+} from "../setup";
+import { Clock } from "solana-bankrun";
+
+const DAY = 86400;
+
+describe("Subscribe To Feed Instruction", () => {
+  let ctx: TestContext;
+  let feedPDA: PublicKey;
+  let subscriptionPDA: PublicKey;
+
+  function subscribe(feed: PublicKey, durationSeconds: number | BN) {
+    const [subscription] = PublicKey.findProgramAddressSync(
+      [Buffer.from("subscription"), feed.toBuffer(), ctx.authority.publicKey.toBuffer()],
+      ctx.molphaProgram.programId
+    );
+    return ctx.molphaProgram.methods
+      .subscribeToFeed(new BN(durationSeconds.toString()))
+      .accountsPartial({
+        feed,
+        subscription,
+        subscriber: ctx.authority.publicKey,
+        subscriberTokenAccount: ctx.userTokenAccount,
+        programTokenAccount: ctx.programTokenAccount,
+        protocolConfig: ctx.protocolConfigPDA,
+        underlyingToken: ctx.underlyingTokenMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      });
+  }
+
+  function readSubscribedAnswer() {
+    return ctx.molphaProgram.methods
+      .readSubscribedAnswer(new BN(3600), 1)
+      .accountsPartial({
+        feed: feedPDA,
+        subscription: subscriptionPDA,
+        subscriber: ctx.authority.publicKey,
+      })
+      .view();
+  }
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeProtocol(ctx);
+    await addTestNodes(ctx, 2);
+    feedPDA = await createTestFeed(ctx, createFeedParams("shared-sub-feed", { public: {} }));
+    [subscriptionPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("subscription"), feedPDA.toBuffer(), ctx.authority.publicKey.toBuffer()],
+      ctx.molphaProgram.programId
+    );
+
+    const clock = await ctx.provider.context.banksClient.getClock();
+    await publishTestAnswer(ctx, feedPDA, createAnswerReport(100, Number(clock.unixTimestamp)));
+  });
+
+  it("Creates a subscription and adds its payment to the feed pool", async () => {
+    const feedBefore = await ctx.molphaProgram.account.feed.fetch(feedPDA);
+    const tokensBefore = await getAccount(ctx.provider.connection, ctx.userTokenAccount);
+    const clock = await ctx.provider.context.banksClient.getClock();
+
+    await subscribe(feedPDA, 2 * DAY).rpc();
+
+    const cost = feedBefore.pricePerSecondScaled.muln(2 * DAY).div(new BN(1000000));
+    const subscription = await ctx.molphaProgram.account.subscription.fetch(subscriptionPDA);
+    assert.ok(subscription.feed.equals(feedPDA));
+    assert.ok(subscription.subscriber.equals(ctx.authority.publicKey));
+    assert.equal(subscription.dueTime.toNumber(), Number(clock.unixTimestamp) + 2 * DAY);
+    assert.equal(subscription.totalPaid.toString(), cost.toString());
+
+    const feedAfter = await ctx.molphaProgram.account.feed.fetch(feedPDA);
+    assert.equal(feedAfter.balance.toString(), feedBefore.balance.add(cost).toString());
+    // Keeps the feed live for the subscriber without extending the authority's time
+    assert.equal(
+      feedAfter.subscriptionDueTime.toNumber(),
+      feedBefore.subscriptionDueTime.toNumber()
+    );
+    assert.equal(feedAfter.subscribedUntil.toNumber(), subscription.dueTime.toNumber());
+
+    const tokensAfter = await getAccount(ctx.provider.connection, ctx.userTokenAccount);
+    assert.equal((tokensBefore.amount - tokensAfter.amount).toString(), cost.toString());
+  });
+
+  it("Extends an existing subscription", async () => {
+    const before = await ctx.molphaProgram.account.subscription.fetch(subscriptionPDA);
+    await subscribe(feedPDA, DAY).rpc();
+    const after = await ctx.molphaProgram.account.subscription.fetch(subscriptionPDA);
+    assert.equal(after.dueTime.toNumber(), before.dueTime.toNumber() + DAY);
+    assert.equal(after.createdAt.toNumber(), before.createdAt.toNumber());
+  });
+
+  it("Reads the answer for an active subscriber", async () => {
+    const answer = await readSubscribedAnswer();
+    assert.equal(answer.signerCount, 2);
+  });
+
+  it("Fails for personal feeds", async () => {
+    const personalFeed = await createTestFeed(
+      ctx,
+      createFeedParams("shared-sub-personal", { personal: {} })
+    );
+    try {
+      await subscribe(personalFeed, DAY).rpc();
+      assert.fail("Should have failed with NotSupported");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("NotSupported") ||
+          error.message.includes("custom program error")
+      );
+    }
+  });
+
+  it("Fails instead of overflowing on huge durations", async () => {
+    try {
+      await subscribe(feedPDA, new BN("18446744073709551615")).rpc();
+      assert.fail("Should have failed with ArithmeticOverflow");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("ArithmeticOverflow") ||
+          error.message.includes("custom program error")
+      );
+    }
+  });
+
+  it("Fails for durations shorter than a day", async () => {
+    try {
+      await subscribe(feedPDA, DAY - 1).rpc();
+      assert.fail("Should have failed with MinimumExtensionTime");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("MinimumExtensionTime") ||
+          error.message.includes("custom program error")
+      );
+    }
+  });
+
+  it("Fails to read once the subscription has run out", async () => {
+    const subscription = await ctx.molphaProgram.account.subscription.fetch(subscriptionPDA);
+    const clock = await ctx.provider.context.banksClient.getClock();
+    ctx.provider.context.setClock(
+      new Clock(
+        clock.slot,
+        clock.epochStartTimestamp,
+        clock.epoch,
+        clock.leaderScheduleEpoch,
+        BigInt(subscription.dueTime.toNumber() + 1)
+      )
+    );
+
+    try {
+      await readSubscribedAnswer();
+      assert.fail("Should have failed with SubscriptionExpired");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("SubscriptionExpired") ||
+          error.message.includes("custom program error") ||
+          error.message.includes("Simulation failed")
+      );
+    }
+  });
+});
@@ -0,0 +1,245 @@
+import { assert } from "chai";
+import { PublicKey, SystemProgram, Transaction } from "@solana/web3.js";
+import { BN } from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAccount } from "@solana/spl-token";
+import {
+  setupTestContext,
+  initializeProtocol,
+  TestContext,
+  createFeedParams,
+  createTestFeed,
+  addTestNodes,
+  createAnswerReport,
+  publishTestAnswer,
+  getFeedAccountPda,
+} from "../setup";
+import { Clock } from "solana-bankrun";
+
+const DAY = 86400;
+const GRACE_PERIOD = 3600;
+
+describe("Subscription Grace Period", () => {
+  let ctx: TestContext;
+  let feedPDA: PublicKey;
+
+  async function setTime(unixTimestamp: number) {
+    const clock = await ctx.provider.context.banksClient.getClock();
+    ctx.provider.context.setClock(
+      new Clock(
+        clock.slot,
+        clock.epochStartTimestamp,
+        clock.epoch,
+        clock.leaderScheduleEpoch,
+        BigInt(unixTimestamp)
+      )
+    );
+  }
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeProtocol(ctx);
+    await addTestNodes(ctx, 2);
+    await ctx.molphaProgram.methods
+      .updateProtocolConfig({
+        basePricePerSecondScaled: new BN(1_000_000),
+        frequencyCoefficient: new BN(0),
+        signersCoefficient: new BN(0),
+        rewardPercentage: new BN(0),
+        priorityFeeBufferPercentage: 100,
+        maxPriorityFeeCoverage: new BN(0),
+        priorityFeeSmoothingWindow: 0,
+        transmitterWindow: new BN(0),
+        transmitterFallbackDelay: new BN(0),
+        crankTipBps: 0,
+        gracePeriod: new BN(GRACE_PERIOD),
+        lowSubscriptionThreshold: new BN(2 * DAY),
+        lowPriorityBudgetThreshold: new BN(0),
+      })
+      .accountsPartial({
+        protocolConfig: ctx.protocolConfigPDA,
+        authority: ctx.authority.publicKey,
+      })
+      .rpc();
+
+    feedPDA = await createTestFeed(ctx, createFeedParams("grace-feed-test", { public: {} }), DAY);
+  });
+
+  it("Keeps publishing during the grace period and accrues debt", async () => {
+    const feed = await ctx.molphaProgram.account.feed.fetch(feedPDA);
+    const dueTime = feed.subscriptionDueTime.toNumber();
+    assert.isTrue(feed.pricePerSecondScaled.gtn(0));
+
+    await setTime(dueTime + 100);
+    await publishTestAnswer(ctx, feedPDA, createAnswerReport(100, dueTime + 100));
+
+    const updated = await ctx.molphaProgram.account.feed.fetch(feedPDA);
+    assert.equal(updated.latestRoundId.toNumber(), 1);
+    assert.equal(
+      updated.graceDebt.toString(),
+      new BN(100).mul(feed.pricePerSecondScaled).divn(1_000_000).toString()
+    );
+    assert.equal(updated.graceAccruedUntil.toNumber(), dueTime + 100);
+  });
+
+  it("Fails to publish once the grace period has passed", async () => {
+    const feed = await ctx.molphaProgram.account.feed.fetch(feedPDA);
+    const dueTime = feed.subscriptionDueTime.toNumber();
+    await setTime(dueTime + GRACE_PERIOD);
+
+    try {
+      await publishTestAnswer(ctx, feedPDA, createAnswerReport(101, dueTime + GRACE_PERIOD));
+      assert.fail("Should have failed with SubscriptionExpired");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("SubscriptionExpired") ||
+          error.message.includes("custom program error"),
+        error.message
+      );
+    }
+  });
+
+  it("Settles the debt on the next extension", async () => {
+    const feed = await ctx.molphaProgram.account.feed.fetch(feedPDA);
+    const before = await getAccount(ctx.provider.connection, ctx.userTokenAccount);
+    await ctx.molphaProgram.methods
+      .extendSubscription(new BN(DAY), new BN(0))
+      .accountsPartial({
+        feed: feedPDA,
+        authority: ctx.authority.publicKey,
+        protocolConfig: ctx.protocolConfigPDA,
+        userTokenAccount: ctx.userTokenAccount,
+        programTokenAccount: ctx.programTokenAccount,
+        underlyingToken: ctx.underlyingTokenMint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+    const after = await getAccount(ctx.provider.connection, ctx.userTokenAccount);
+    const updated = await ctx.molphaProgram.account.feed.fetch(feedPDA);
+
+    const baseCost = new BN(DAY).mul(feed.pricePerSecondScaled).divn(1_000_000);
+    assert.equal(
+      (before.amount - after.amount).toString(),
+      baseCost.add(feed.graceDebt).toString()
+    );
+    assert.equal(updated.graceDebt.toNumber(), 0);
+  });
+
+  describe("Multi-transaction rounds", () => {
+    let roundFeedPDA: PublicKey;
+    let dueTime: number;
+
+    before(async () => {
+      const fund = new Transaction();
+      for (const node of ctx.nodes.slice(0, 2)) {
+        fund.add(
+          SystemProgram.transfer({
+            fromPubkey: ctx.authority.publicKey,
+            toPubkey: node.publicKey,
+            lamports: 1_000_000_000,
+          })
+        );
+      }
+      await ctx.provider.sendAndConfirm(fund, [ctx.authority.payer]);
+
+      roundFeedPDA = await createTestFeed(
+        ctx,
+        createFeedParams("grace-round-test", { public: {} }),
+        DAY
+      );
+      const feed = await ctx.molphaProgram.account.feed.fetch(roundFeedPDA);
+      dueTime = feed.subscriptionDueTime.toNumber();
+    });
+
+    it("Finalizes a round during the grace period and accrues debt", async () => {
+      await setTime(dueTime + 100);
+      const [roundPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("round"), roundFeedPDA.toBuffer(), new BN(1).toBuffer("le", 8)],
+        ctx.molphaProgram.programId
+      );
+      for (const node of ctx.nodes.slice(0, 2)) {
+        await ctx.molphaProgram.methods
+          .submitObservation(new BN(1), createAnswerReport(100, dueTime + 99))
+          .accountsPartial({
+            feed: roundFeedPDA,
+            round: roundPDA,
+            nodeRegistry: ctx.nodeRegistryPDA,
+            protocolConfig: ctx.protocolConfigPDA,
+            node: node.publicKey,
+          })
+          .signers([node])
+          .rpc();
+      }
+
+      const feed = await ctx.molphaProgram.account.feed.fetch(roundFeedPDA);
+      assert.equal(feed.latestRoundId.toNumber(), 1);
+      assert.equal(
+        feed.graceDebt.toString(),
+        new BN(100).mul(feed.pricePerSecondScaled).divn(1_000_000).toString()
+      );
+    });
+
+    it("Deducts the grace debt from the close refund", async () => {
+      const feed = await ctx.molphaProgram.account.feed.fetch(roundFeedPDA);
+      assert.isTrue(feed.graceDebt.gtn(0));
+      const expectedRefund = BN.min(
+        feed.priorityFeeAllowance.sub(feed.consumedPriorityFees).sub(feed.graceDebt),
+        feed.balance
+      );
+
+      const before = await getAccount(ctx.provider.connection, ctx.userTokenAccount);
+      await ctx.molphaProgram.methods
+        .closeFeed()
+        .accountsPartial({
+          feed: roundFeedPDA,
+          history: null,
+          readers: getFeedAccountPda(ctx.molphaProgram.programId, "readers", roundFeedPDA)[0],
+          successor: getFeedAccountPda(ctx.molphaProgram.programId, "successor", roundFeedPDA)[0],
+          autoRenew: getFeedAccountPda(ctx.molphaProgram.programId, "auto_renew", roundFeedPDA)[0],
+          authority: ctx.authority.publicKey,
+          userTokenAccount: ctx.userTokenAccount,
+          programTokenAccount: ctx.programTokenAccount,
+          protocolConfig: ctx.protocolConfigPDA,
+          underlyingToken: ctx.underlyingTokenMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      const after = await getAccount(ctx.provider.connection, ctx.userTokenAccount);
+
+      assert.equal((after.amount - before.amount).toString(), expectedRefund.toString());
+    });
+  });
+
+  it("Updates the config of a feed in its grace period", async () => {
+    const personalFeedPDA = await createTestFeed(
+      ctx,
+      createFeedParams("grace-config-test", { personal: {} }),
+      DAY
+    );
+    const feed = await ctx.molphaProgram.account.feed.fetch(personalFeedPDA);
+    const dueTime = feed.subscriptionDueTime.toNumber();
+    await setTime(dueTime + 100);
+
+    const params = createFeedParams("grace-config-test", { personal: {} });
+    await ctx.molphaProgram.methods
+      .updateFeedConfig({
+        minSignaturesThreshold: params.minSignaturesThreshold,
+        frequency: params.frequency.muln(2),
+        updatePolicy: params.updatePolicy,
+        ipfsCid: params.ipfsCid,
+        jobId: params.jobId,
+      })
+      .accountsPartial({
+        feed: personalFeedPDA,
+        authority: ctx.authority.publicKey,
+        protocolConfig: ctx.protocolConfigPDA,
+      })
+      .rpc();
+
+    const updated = await ctx.molphaProgram.account.feed.fetch(personalFeedPDA);
+    assert.equal(updated.frequency.toString(), params.frequency.muln(2).toString());
+    assert.equal(updated.subscriptionDueTime.toNumber(), dueTime);
+  });
+});
@@ -11,6 +11,10 @@ import {
   createTestDataSourceInfo,
   getDataSourcePda,
   createFeedParams,
+  createAnswerReport,
+  reportSigningMessage,
+  transmitterAccounts,
+  decodeFixedString,
 } from "../setup";
 
 async function safePastOnchainTimestamp(
@@ -95,10 +99,6 @@ describe("Subscription Integration Tests", () => {
           Buffer.from("feed"),
           ctx.authority.publicKey.toBuffer(),
           Buffer.from(feedParams.name),
-          Buffer.from([0]), // Public feed type
-          Buffer.from([feedParams.minSignaturesThreshold]),
-          feedParams.frequency.toBuffer("le", 8),
-          Buffer.from(feedParams.jobId),
         ],
         ctx.molphaProgram.programId
       );
@@ -137,10 +137,10 @@ describe("Subscription Integration Tests", () => {
 
       // Verify the feed was created correctly
       assert.ok(feed.authority.equals(ctx.authority.publicKey));
-      assert.deepEqual(feed.feedType, { public: {} });
+      assert.equal(feed.feedType, 0); // FeedType::Public
       assert.equal(feed.minSignaturesThreshold, 2);
       assert.equal(feed.frequency.toNumber(), 300);
-      assert.equal(feed.ipfsCid, "QmTestCID123456789");
+      assert.equal(decodeFixedString(feed.ipfsCid), "QmTestCID123456789");
       assert.deepEqual(feed.dataSource, dataSourcePDA);
       assert.deepEqual(
         feed.jobId,
@@ -167,10 +167,6 @@ describe("Subscription Integration Tests", () => {
           Buffer.from("feed"),
           ctx.authority.publicKey.toBuffer(),
           Buffer.from(feedParams.name),
-          Buffer.from([0]), // Public feed type
-          Buffer.from([feedParams.minSignaturesThreshold]),
-          feedParams.frequency.toBuffer("le", 8),
-          Buffer.from(feedParams.jobId),
         ],
         ctx.molphaProgram.programId
       );
@@ -355,13 +351,13 @@ describe("Subscription Integration Tests", () => {
         .rpc();
 
       // Create a test answer with a timestamp further in the past
-      const answer = {
-        value: Array.from(Buffer.from("12345678901234567890123456789012")), // 32 bytes
-        timestamp: await safePastOnchainTimestamp(ctx.provider, 30), // Use timestamp 30 seconds in the past
-      };
+      const answer = createAnswerReport(
+        12345678,
+        await safePastOnchainTimestamp(ctx.provider, 30) // Use timestamp 30 seconds in the past
+      );
 
       // Create signature instructions like the working tests
-      const message = Buffer.from(answer.value);
+      const message = reportSigningMessage(feedPDA, answer);
       const preIxs: anchor.web3.TransactionInstruction[] = [];
 
       // Add signatures from both test nodes (need 2 since minSignaturesThreshold is 2)
@@ -384,19 +380,17 @@ describe("Subscription Integration Tests", () => {
           nodeRegistry: ctx.nodeRegistryPDA,
           protocolConfig: ctx.protocolConfigPDA,
           instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          ...transmitterAccounts(ctx),
         })
         .preInstructions(preIxs)
         .rpc();
 
       // Verify the answer was published
       const updatedFeed = await ctx.molphaProgram.account.feed.fetch(feedPDA);
-      assert.deepEqual(
-        updatedFeed.latestAnswer.value,
-        Array.from(answer.value)
-      );
+      assert.deepEqual(updatedFeed.latestAnswer.value, answer.value);
       assert.equal(
-        updatedFeed.latestAnswer.timestamp.toNumber(),
-        answer.timestamp.toNumber()
+        updatedFeed.latestAnswer.observedTo.toNumber(),
+        answer.observedTo.toNumber()
       );
     });
 
@@ -414,10 +408,6 @@ describe("Subscription Integration Tests", () => {
           Buffer.from("feed"),
           ctx.authority.publicKey.toBuffer(),
           Buffer.from(shortFeedParams.name),
-          Buffer.from([0]), // Public feed type
-          Buffer.from([shortFeedParams.minSignaturesThreshold]),
-          shortFeedParams.frequency.toBuffer("le", 8),
-          Buffer.from(shortFeedParams.jobId),
         ],
         ctx.molphaProgram.programId
       );
@@ -456,10 +446,10 @@ describe("Subscription Integration Tests", () => {
       await new Promise((resolve) => setTimeout(resolve, 2000)); // Wait 2 seconds
 
       // Try to publish answer
-      const answer = {
-        value: Array.from(Buffer.from("12345678901234567890123456789012")), // 32 bytes
-        timestamp: await safePastOnchainTimestamp(ctx.provider, 30), // Use timestamp 30 seconds in the past
-      };
+      const answer = createAnswerReport(
+        12345678,
+        await safePastOnchainTimestamp(ctx.provider, 30) // Use timestamp 30 seconds in the past
+      );
 
       try {
         await ctx.molphaProgram.methods
@@ -469,6 +459,7 @@ describe("Subscription Integration Tests", () => {
             nodeRegistry: ctx.nodeRegistryPDA,
             protocolConfig: ctx.protocolConfigPDA,
             instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            ...transmitterAccounts(ctx),
           })
           .rpc();
 
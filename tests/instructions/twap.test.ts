@@ -0,0 +1,135 @@
+import { assert } from "chai";
+import { PublicKey } from "@solana/web3.js";
+import { BN } from "@coral-xyz/anchor";
+import {
+  setupTestContext,
+  initializeProtocol,
+  TestContext,
+  createFeedParams,
+  createTestFeed,
+  addTestNodes,
+  createAnswerReport,
+  fetchFeedHistory,
+  getFeedHistoryPda,
+  publishTestAnswer,
+} from "../setup";
+
+describe("TWAP and EMA", () => {
+  let ctx: TestContext;
+  let feedPDA: PublicKey;
+  let historyPDA: PublicKey;
+  let now: number;
+
+  function getTwap(windowSeconds: number) {
+    return ctx.molphaProgram.methods
+      .getTwap(new BN(windowSeconds))
+      .accountsPartial({
+        feed: feedPDA,
+        history: historyPDA,
+        readers: null,
+        instructions: null,
+      })
+      .view();
+  }
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeProtocol(ctx);
+    await addTestNodes(ctx, 2);
+
+    const params = createFeedParams("twap-feed-test", { public: {} });
+    feedPDA = await createTestFeed(ctx, {
+      ...params,
+      updatePolicy: { ...params.updatePolicy, emaPeriod: new BN(10) },
+    });
+    [historyPDA] = getFeedHistoryPda(ctx.molphaProgram.programId, feedPDA);
+    await ctx.molphaProgram.methods
+      .createFeedHistory(10)
+      .accountsPartial({
+        feed: feedPDA,
+        history: historyPDA,
+        authority: ctx.authority.publicKey,
+      })
+      .rpc();
+
+    const clock = await ctx.provider.context.banksClient.getClock();
+    now = Number(clock.unixTimestamp);
+
+    // 100 from now-60, 200 from now-50, 400 from now-30
+    for (const [value, age] of [[100, 60], [200, 50], [400, 30]]) {
+      await publishTestAnswer(
+        ctx,
+        feedPDA,
+        createAnswerReport(value, now - age),
+        ctx.nodes.slice(0, 2),
+        historyPDA
+      );
+    }
+  });
+
+  it("Records the accumulator with each history entry", async () => {
+    const history = await fetchFeedHistory(ctx, historyPDA);
+    assert.deepEqual(
+      history.entries.map((entry) => entry.priceCumulative.toNumber()),
+      [0, 100 * 10, 100 * 10 + 200 * 20]
+    );
+  });
+
+  it("Returns the TWAP over a window", async () => {
+    const result = await getTwap(40);
+    assert.equal(result.twap.toNumber(), (200 * 10 + 400 * 30) / 40);
+    assert.equal(result.windowEnd.toNumber(), now);
+    assert.equal(result.windowStart.toNumber(), now - 40);
+  });
+
+  it("Returns the TWAP over the whole history", async () => {
+    const result = await getTwap(60);
+    assert.equal(result.twap.toNumber(), Math.floor((100 * 10 + 200 * 20 + 400 * 30) / 60));
+  });
+
+  it("Returns the EMA of the answers", async () => {
+    // 100, then +(200 - 100) * 10 / 20, then +(400 - 150) * 20 / 30
+    const result = await getTwap(30);
+    assert.equal(result.ema.toNumber(), 316);
+  });
+
+  it("Fails for windows older than the history", async () => {
+    try {
+      await getTwap(70);
+      assert.fail("Should have failed with WindowNotCovered");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("WindowNotCovered") ||
+          error.message.includes("custom program error") ||
+          error.message.includes("Simulation failed")
+      );
+    }
+  });
+
+  it("Rejects windows across an exponent change and restarts the EMA", async () => {
+    // 400.00 from now-10, in the new scale
+    await publishTestAnswer(
+      ctx,
+      feedPDA,
+      createAnswerReport(40_000, now - 10, -2),
+      ctx.nodes.slice(0, 2),
+      historyPDA
+    );
+
+    try {
+      await getTwap(20);
+      assert.fail("Should have failed with ExponentChanged");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("ExponentChanged") ||
+          error.message.includes("custom program error") ||
+          error.message.includes("Simulation failed")
+      );
+    }
+
+    const result = await getTwap(10);
+    assert.equal(result.twap.toNumber(), 40_000);
+    assert.equal(result.ema.toNumber(), 40_000);
+    assert.equal(result.exponent, -2);
+  });
+});
@@ -0,0 +1,146 @@
+import * as anchor from "@coral-xyz/anchor";
+import { assert } from "chai";
+import { PublicKey } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import nacl from "tweetnacl";
+import {
+  setupTestContext,
+  initializeProtocol,
+  TestContext,
+  createFeedParams,
+  createTestFeed,
+  addTestNodes,
+  createAnswerReport,
+  encodeValue,
+  leafHash,
+  AnswerReport,
+  publishTestAnswer,
+} from "../setup";
+
+function getPriceUpdatePda(programId: PublicKey, feed: PublicKey, consumer: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("price_update"), feed.toBuffer(), consumer.toBuffer()],
+    programId
+  );
+}
+
+describe("Verify Report Instruction", () => {
+  let ctx: TestContext;
+  let feedPDA: PublicKey;
+  let otherFeedPDA: PublicKey;
+
+  async function verifyReport(
+    report: AnswerReport,
+    signedFor: PublicKey,
+    priceUpdate: PublicKey | null,
+    maxAgeSeconds = 60
+  ) {
+    // A single report is its own Merkle root
+    const message = leafHash(signedFor, report);
+    const preIxs = [ctx.nodes[0], ctx.nodes[1]].map((signer) =>
+      anchor.web3.Ed25519Program.createInstructionWithPublicKey({
+        publicKey: signer.publicKey.toBytes(),
+        message,
+        signature: nacl.sign.detached(message, signer.secretKey),
+      })
+    );
+    await ctx.molphaProgram.methods
+      .verifyReport(report, [], new anchor.BN(maxAgeSeconds))
+      .accountsPartial({
+        feed: feedPDA,
+        nodeRegistry: ctx.nodeRegistryPDA,
+        priceUpdate,
+        consumer: ctx.authority.publicKey,
+        consumerTokenAccount: ctx.userTokenAccount,
+        programTokenAccount: ctx.programTokenAccount,
+        protocolConfig: ctx.protocolConfigPDA,
+        underlyingToken: ctx.underlyingTokenMint,
+        instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      })
+      .preInstructions(preIxs)
+      .rpc();
+  }
+
+  async function observedTo(secondsInPast: number) {
+    const clock = await ctx.provider.context.banksClient.getClock();
+    return Number(clock.unixTimestamp) - secondsInPast;
+  }
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeProtocol(ctx);
+    await addTestNodes(ctx, 2);
+
+    feedPDA = await createTestFeed(ctx, createFeedParams("pull-feed-test", { public: {} }));
+    otherFeedPDA = await createTestFeed(ctx, createFeedParams("pull-feed-other", { public: {} }));
+  });
+
+  it("Verifies a report and stores it in the consumer's price update", async () => {
+    const [priceUpdatePDA] = getPriceUpdatePda(
+      ctx.molphaProgram.programId,
+      feedPDA,
+      ctx.authority.publicKey
+    );
+    const report = createAnswerReport(4_200, await observedTo(5), -2);
+
+    await verifyReport(report, feedPDA, priceUpdatePDA);
+
+    const priceUpdate = await ctx.molphaProgram.account.priceUpdate.fetch(priceUpdatePDA);
+    assert.ok(priceUpdate.feed.equals(feedPDA));
+    assert.ok(priceUpdate.consumer.equals(ctx.authority.publicKey));
+    assert.deepEqual(priceUpdate.answer.value, encodeValue(4_200));
+    assert.equal(priceUpdate.answer.signerCount, 2);
+
+    // Pull mode leaves the feed itself untouched
+    const feed = await ctx.molphaProgram.account.feed.fetch(feedPDA);
+    assert.equal(feed.latestRoundId.toNumber(), 0);
+  });
+
+  it("Verifies a report without a price update account", async () => {
+    const report = createAnswerReport(4_300, await observedTo(4), -2);
+    await verifyReport(report, feedPDA, null);
+  });
+
+  it("Rejects reports signed for another feed", async () => {
+    const report = createAnswerReport(4_400, await observedTo(3), -2);
+    try {
+      await verifyReport(report, otherFeedPDA, null);
+      assert.fail("Should have failed with not enough signatures");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("NotEnoughSignatures") ||
+          error.message.includes("custom program error")
+      );
+    }
+  });
+
+  it("Rejects reports older than the maximum age", async () => {
+    const report = createAnswerReport(4_500, await observedTo(30), -2);
+    try {
+      await verifyReport(report, feedPDA, null, 10);
+      assert.fail("Should have failed with StaleAnswer");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("StaleAnswer") ||
+          error.message.includes("custom program error")
+      );
+    }
+  });
+
+  it("Rejects reports older than the feed's latest answer", async () => {
+    await publishTestAnswer(ctx, feedPDA, createAnswerReport(4_600, await observedTo(2), -2));
+
+    const report = createAnswerReport(4_500, await observedTo(10), -2);
+    try {
+      await verifyReport(report, feedPDA, null);
+      assert.fail("Should have failed with PastTimestamp");
+    } catch (error: any) {
+      assert.ok(
+        error.message.includes("PastTimestamp") ||
+          error.message.includes("custom program error")
+      );
+    }
+  });
+});
@@ -10,6 +10,10 @@ import {
   createTestDataSourceInfo,
   getDataSourcePda,
   createFeedParams,
+  createAnswerReport,
+  reportSigningMessage,
+  transmitterAccounts,
+  decodeFixedString,
 } from "../setup";
 import { BankrunProvider } from "anchor-bankrun";
 
@@ -103,8 +107,10 @@ describe("Integration Tests: Complete Oracle Flow", () => {
         name: feedId,
         jobId: Array.from(Buffer.from(feedId.padEnd(32, "\0"))),
         feedType: { public: {} },
+        valueType: { i128Price: {} },
         minSignaturesThreshold: 2, // Require 2 out of 3 nodes
         frequency: new anchor.BN(600), // 10 minutes
+        updatePolicy: { minUpdateInterval: new anchor.BN(0), deviationThresholdBps: 0, heartbeat: new anchor.BN(0), maxDeviationBps: 0, outlierSignaturesThreshold: 0, emaPeriod: new anchor.BN(0) },
         ipfsCid: "QmIntegrationTest123",
       };
 
@@ -113,10 +119,6 @@ describe("Integration Tests: Complete Oracle Flow", () => {
           Buffer.from("feed"),
           ctx.authority.publicKey.toBuffer(),
           Buffer.from(feedParams.name),
-          Buffer.from([0]), // FeedType::Public = 0
-          Buffer.from([feedParams.minSignaturesThreshold]),
-          feedParams.frequency.toBuffer("le", 8),
-          Buffer.from(feedParams.jobId),
         ],
         ctx.molphaProgram.programId
       );
@@ -149,26 +151,21 @@ describe("Integration Tests: Complete Oracle Flow", () => {
       console.log("feedPDA:", feedPDA.toBase58());
       const feed = await ctx.molphaProgram.account.feed.fetch(feedPDA);
       assert.ok(feed.authority.equals(ctx.authority.publicKey));
-      assert.deepEqual(feed.feedType, { public: {} });
+      assert.equal(feed.feedType, 0); // FeedType::Public
       assert.deepEqual(feed.dataSource, dataSourcePDA);
       assert.equal(feed.minSignaturesThreshold, 2);
       assert.equal(feed.frequency.toNumber(), 600);
-      assert.equal(feed.ipfsCid, "QmIntegrationTest123");
+      assert.equal(decodeFixedString(feed.ipfsCid), "QmIntegrationTest123");
 
       // Step 4: Publish data to the feed
       console.log("Step 4: Publishing data to feed...");
-      const answer = {
-        value: Array.from(
-          Buffer.from(
-            "5000000000000000000000000000000000000000000000000000000000000000",
-            "hex"
-          )
-        ), // 32 bytes representing price data
-        timestamp: await safePastOnchainTimestamp(ctx.molphaProgram.provider, 10), // 10 seconds ago
-      };
+      const answer = createAnswerReport(
+        5000,
+        await safePastOnchainTimestamp(ctx.molphaProgram.provider, 10)
+      );
 
-      // Create message for signature verification (must match answer.value)
-      const message = Buffer.from(answer.value);
+      // Create message for signature verification (must match the serialized report)
+      const message = reportSigningMessage(feedPDA, answer);
 
       // Create transaction with Ed25519 signatures from 2 nodes
       const signers = [nodesToAdd[0], nodesToAdd[1]]; // Use 2 signers to meet threshold
@@ -191,6 +188,7 @@ describe("Integration Tests: Complete Oracle Flow", () => {
           feed: feedPDA,
           nodeRegistry: ctx.nodeRegistryPDA,
           instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          ...transmitterAccounts(ctx),
         })
         .preInstructions(preIxs)
         .rpc();
@@ -198,11 +196,9 @@ describe("Integration Tests: Complete Oracle Flow", () => {
       // Verify the answer was published
       const updatedFeed = await ctx.molphaProgram.account.feed.fetch(feedPDA);
       assert.deepEqual(updatedFeed.latestAnswer.value, answer.value);
-      assert.ok(updatedFeed.latestAnswer.timestamp.eq(answer.timestamp));
+      assert.ok(updatedFeed.latestAnswer.observedTo.eq(answer.observedTo));
 
-      // Verify answer history was updated
-      assert.equal(updatedFeed.answerHistory.length, 1);
-      assert.deepEqual(updatedFeed.answerHistory[0].value, answer.value);
+      assert.equal(updatedFeed.latestRoundId.toNumber(), 1);
 
       console.log("✅ Complete public feed workflow executed successfully!");
     });
@@ -261,8 +257,10 @@ describe("Integration Tests: Complete Oracle Flow", () => {
         name: feedId,
         jobId: Array.from(Buffer.from(feedId.padEnd(32, "\0"))),
         feedType: { personal: {} },
+        valueType: { i128Price: {} },
         minSignaturesThreshold: 1, // Require only 1 signature for personal feed
         frequency: new anchor.BN(900), // 15 minutes
+        updatePolicy: { minUpdateInterval: new anchor.BN(0), deviationThresholdBps: 0, heartbeat: new anchor.BN(0), maxDeviationBps: 0, outlierSignaturesThreshold: 0, emaPeriod: new anchor.BN(0) },
         ipfsCid: "QmPrivateIntegrationTest",
       };
 
@@ -271,10 +269,6 @@ describe("Integration Tests: Complete Oracle Flow", () => {
           Buffer.from("feed"),
           ctx.authority.publicKey.toBuffer(),
           Buffer.from(feedParams.name),
-          Buffer.from([1]), // FeedType::Personal = 1
-          Buffer.from([feedParams.minSignaturesThreshold]),
-          feedParams.frequency.toBuffer("le", 8),
-          Buffer.from(feedParams.jobId),
         ],
         ctx.molphaProgram.programId
       );
@@ -322,17 +316,12 @@ describe("Integration Tests: Complete Oracle Flow", () => {
 
       // Step 5: Publish data to the personal feed
       console.log("Step 5: Publishing data to personal feed...");
-      const answer = {
-        value: Array.from(
-          Buffer.from(
-            "7500000000000000000000000000000000000000000000000000000000000000",
-            "hex"
-          )
-        ), // 32 bytes representing different price data
-        timestamp: await safePastOnchainTimestamp(ctx.molphaProgram.provider, 5), // 5 seconds ago
-      };
+      const answer = createAnswerReport(
+        7000,
+        await safePastOnchainTimestamp(ctx.molphaProgram.provider, 5)
+      );
 
-      const message = Buffer.from(answer.value);
+      const message = reportSigningMessage(feedPDA, answer);
 
       // Create signature instructions like the working tests
       const preIxs: anchor.web3.TransactionInstruction[] = [];
@@ -355,6 +344,7 @@ describe("Integration Tests: Complete Oracle Flow", () => {
           nodeRegistry: ctx.nodeRegistryPDA,
           protocolConfig: ctx.protocolConfigPDA,
           instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          ...transmitterAccounts(ctx),
         })
         .preInstructions(preIxs)
         .rpc();
@@ -362,7 +352,7 @@ describe("Integration Tests: Complete Oracle Flow", () => {
       // Verify the answer was published
       const updatedFeed = await ctx.molphaProgram.account.feed.fetch(feedPDA);
       assert.deepEqual(updatedFeed.latestAnswer.value, answer.value);
-      assert.ok(updatedFeed.latestAnswer.timestamp.eq(answer.timestamp));
+      assert.ok(updatedFeed.latestAnswer.observedTo.eq(answer.observedTo));
 
       console.log("✅ Complete personal feed workflow executed successfully!");
     });
@@ -397,8 +387,10 @@ describe("Integration Tests: Complete Oracle Flow", () => {
         name: feedId,
         jobId: Array.from(Buffer.from(feedId.padEnd(32, "\0"))),
         feedType: { public: {} },
+        valueType: { i128Price: {} },
         minSignaturesThreshold: 3, // Require 3 signatures
         frequency: new anchor.BN(300),
+        updatePolicy: { minUpdateInterval: new anchor.BN(0), deviationThresholdBps: 0, heartbeat: new anchor.BN(0), maxDeviationBps: 0, outlierSignaturesThreshold: 0, emaPeriod: new anchor.BN(0) },
         ipfsCid: "QmHighThreshold",
       };
 
@@ -407,10 +399,6 @@ describe("Integration Tests: Complete Oracle Flow", () => {
           Buffer.from("feed"),
           ctx.authority.publicKey.toBuffer(),
           Buffer.from(feedParams.name),
-          Buffer.from([0]),
-          Buffer.from([feedParams.minSignaturesThreshold]),
-          feedParams.frequency.toBuffer("le", 8),
-          Buffer.from(feedParams.jobId),
         ],
         ctx.molphaProgram.programId
       );
@@ -439,17 +427,12 @@ describe("Integration Tests: Complete Oracle Flow", () => {
         .rpc();
 
       // Try to publish with only 2 signatures (below threshold of 3)
-      const answer = {
-        value: Array.from(
-          Buffer.from(
-            "1000000000000000000000000000000000000000000000000000000000000000",
-            "hex"
-          )
-        ),
-        timestamp: await safePastOnchainTimestamp(ctx.molphaProgram.provider, 10),
-      };
+      const answer = createAnswerReport(
+        1000,
+        await safePastOnchainTimestamp(ctx.molphaProgram.provider, 10)
+      );
 
-      const message = Buffer.from(answer.value);
+      const message = reportSigningMessage(feedPDA, answer);
       const transaction = new Transaction();
 
       // Add only 2 signatures (below threshold)
@@ -471,6 +454,7 @@ describe("Integration Tests: Complete Oracle Flow", () => {
             feed: feedPDA,
             nodeRegistry: ctx.nodeRegistryPDA,
             instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            ...transmitterAccounts(ctx),
           })
           .instruction()
       );
@@ -547,19 +531,15 @@ describe("Integration Tests: Complete Oracle Flow", () => {
           feedType: config.type,
           minSignaturesThreshold: config.threshold,
           frequency: new anchor.BN(config.frequency),
+          updatePolicy: { minUpdateInterval: new anchor.BN(0), deviationThresholdBps: 0, heartbeat: new anchor.BN(0), maxDeviationBps: 0, outlierSignaturesThreshold: 0, emaPeriod: new anchor.BN(0) },
           ipfsCid: `QmShared${config.id}`,
         };
 
-        const feedTypeValue = config.type.public ? 0 : 1;
         const [feedPDA] = PublicKey.findProgramAddressSync(
           [
             Buffer.from("feed"),
             ctx.authority.publicKey.toBuffer(),
             Buffer.from(feedParams.name),
-            Buffer.from([feedTypeValue]),
-            Buffer.from([feedParams.minSignaturesThreshold]),
-            feedParams.frequency.toBuffer("le", 8),
-            Buffer.from(feedParams.jobId),
           ],
           ctx.molphaProgram.programId
         );
@@ -592,17 +572,12 @@ describe("Integration Tests: Complete Oracle Flow", () => {
 
       // Publish data to both feeds
       for (const { pda, config } of createdFeeds) {
-        const answer = {
-          value: Array.from(
-            Buffer.from(
-              "8000000000000000000000000000000000000000000000000000000000000000",
-              "hex"
-            )
-          ),
-          timestamp: await safePastOnchainTimestamp(ctx.molphaProgram.provider, 15),
-        };
+        const answer = createAnswerReport(
+          8000,
+          await safePastOnchainTimestamp(ctx.molphaProgram.provider, 15)
+        );
 
-        const message = Buffer.from(answer.value);
+        const message = reportSigningMessage(pda, answer);
 
         // Create signature instructions like the working tests
         const preIxs: anchor.web3.TransactionInstruction[] = [];
@@ -626,6 +601,7 @@ describe("Integration Tests: Complete Oracle Flow", () => {
             nodeRegistry: ctx.nodeRegistryPDA,
             protocolConfig: ctx.protocolConfigPDA,
             instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            ...transmitterAccounts(ctx),
           })
           .preInstructions(preIxs)
           .rpc();
@@ -11,6 +11,8 @@ import {
 import { BankrunProvider } from "anchor-bankrun";
 import * as toml from "toml";
 import * as fs from "fs";
+import { createHash } from "crypto";
+import nacl from "tweetnacl";
 import { AddedAccount, startAnchor } from "solana-bankrun";
 
 export interface TestContext {
@@ -277,6 +279,47 @@ export async function initializeProtocol(ctx: TestContext): Promise<void> {
   }
 }
 
+// Creates a mint other than the protocol's underlying token, with a funded
+// authority ATA and a protocol_config ATA, to check foreign mints are rejected
+export function createForeignMint(ctx: TestContext) {
+  const mint = Keypair.generate().publicKey;
+  const userTokenAccount = getAssociatedTokenAddressSync(mint, ctx.authority.publicKey);
+  const programTokenAccount = getAssociatedTokenAddressSync(mint, ctx.protocolConfigPDA, true);
+
+  const mintData = Buffer.alloc(82);
+  mintData.writeUInt32LE(1, 0); // mint_authority_option
+  ctx.authority.publicKey.toBuffer().copy(mintData, 4); // mint_authority
+  mintData.writeBigUInt64LE(BigInt(1000000000000), 36); // supply
+  mintData.writeUInt8(6, 44); // decimals
+  mintData.writeUInt8(1, 45); // is_initialized
+  ctx.provider.context.setAccount(mint, {
+    lamports: 1_000_000_000,
+    executable: false,
+    owner: TOKEN_PROGRAM_ID,
+    data: mintData,
+  });
+
+  const tokenAccounts: [PublicKey, PublicKey, bigint][] = [
+    [userTokenAccount, ctx.authority.publicKey, BigInt(500000000000)],
+    [programTokenAccount, ctx.protocolConfigPDA, BigInt(0)],
+  ];
+  for (const [address, owner, amount] of tokenAccounts) {
+    const data = Buffer.alloc(165);
+    mint.toBuffer().copy(data, 0); // mint
+    owner.toBuffer().copy(data, 32); // owner
+    data.writeBigUInt64LE(amount, 64); // amount
+    data.writeUInt8(1, 108); // state (1 = initialized)
+    ctx.provider.context.setAccount(address, {
+      lamports: 2039280,
+      executable: false,
+      owner: TOKEN_PROGRAM_ID,
+      data,
+    });
+  }
+
+  return { mint, userTokenAccount, programTokenAccount };
+}
+
 // Helper function to create feed parameters
 export function createFeedParams(
   jobId: string,
@@ -286,8 +329,10 @@ export function createFeedParams(
     name: jobId,
     jobId: Array.from(Buffer.from(jobId.padEnd(32, "\0"))),
     feedType: feedType,
+    valueType: { i128Price: {} },
     minSignaturesThreshold: 2,
     frequency: new anchor.BN(300), // 5 minutes as BN
+    updatePolicy: { minUpdateInterval: new anchor.BN(0), deviationThresholdBps: 0, heartbeat: new anchor.BN(0), maxDeviationBps: 0, outlierSignaturesThreshold: 0, emaPeriod: new anchor.BN(0) },
     ipfsCid: "QmTestCID123456789",
   };
 }
@@ -319,4 +364,273 @@ export function getDataSourcePda(programId: PublicKey, authority: PublicKey, nam
     ],
     programId
   );
-}
\ No newline at end of file
+}
+export function getFeedPda(
+  programId: PublicKey,
+  creator: PublicKey,
+  params: ReturnType<typeof createFeedParams>
+) {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("feed"),
+      creator.toBuffer(),
+      Buffer.from(params.name),
+    ],
+    programId
+  );
+}
+
+// Creates a data source and a feed on it, returning the feed PDA
+export async function createTestFeed(
+  ctx: TestContext,
+  params: ReturnType<typeof createFeedParams>,
+  subscriptionDurationSeconds = 86400,
+  priorityFeeBudget = 1000
+): Promise<PublicKey> {
+  const dataSourceInfo = createTestDataSourceInfo(
+    0,
+    "https://api.example.com/price",
+    `${params.name}-source`.slice(0, 32)
+  );
+  const [dataSourcePDA] = getDataSourcePda(
+    ctx.molphaProgram.programId,
+    ctx.authority.publicKey,
+    dataSourceInfo.name,
+    0
+  );
+  await ctx.molphaProgram.methods
+    .createDataSource(dataSourceInfo as any)
+    .accountsPartial({
+      dataSource: dataSourcePDA,
+      authority: ctx.authority.publicKey,
+    })
+    .rpc();
+
+  const [feedPDA] = getFeedPda(
+    ctx.molphaProgram.programId,
+    ctx.authority.publicKey,
+    params
+  );
+  await ctx.molphaProgram.methods
+    .createFeed(
+      params as any,
+      new BN(subscriptionDurationSeconds),
+      new BN(priorityFeeBudget)
+    )
+    .accountsPartial({
+      feed: feedPDA,
+      dataSource: dataSourcePDA,
+      authority: ctx.authority.publicKey,
+      protocolConfig: ctx.protocolConfigPDA,
+      userTokenAccount: ctx.userTokenAccount,
+      programTokenAccount: ctx.programTokenAccount,
+      underlyingToken: ctx.underlyingTokenMint,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+
+  return feedPDA;
+}
+
+// Registers the first `count` test nodes, ignoring already-added ones
+export async function addTestNodes(ctx: TestContext, count: number) {
+  for (let i = 0; i < count; i++) {
+    try {
+      await ctx.molphaProgram.methods
+        .addNode(ctx.nodes[i].publicKey)
+        .accountsPartial({
+          nodeRegistry: ctx.nodeRegistryPDA,
+          authority: ctx.authority.publicKey,
+        })
+        .rpc();
+    } catch (e) {
+      // Ignore error if node already added
+    }
+  }
+}
+
+export interface AnswerReport {
+  value: number[];
+  exponent: number;
+  confidence: BN;
+  observedFrom: BN;
+  observedTo: BN;
+}
+
+// Encodes an integer as a 32-byte big-endian two's complement value word
+export function encodeValue(value: BN | number): number[] {
+  return Array.from(new BN(value).toTwos(256).toArrayLike(Buffer, "be", 32));
+}
+
+// Builds a report observed at a single instant
+export function createAnswerReport(
+  value: BN | number,
+  observedTo: BN | number,
+  exponent = 0,
+  confidence: BN | number = 0
+): AnswerReport {
+  return {
+    value: encodeValue(value),
+    exponent,
+    confidence: new BN(confidence),
+    observedFrom: new BN(observedTo),
+    observedTo: new BN(observedTo),
+  };
+}
+
+// Borsh encoding of the report, which is the message the nodes sign
+export function serializeAnswerReport(report: AnswerReport): Buffer {
+  const exponent = Buffer.alloc(4);
+  exponent.writeInt32LE(report.exponent);
+  return Buffer.concat([
+    Buffer.from(report.value),
+    exponent,
+    report.confidence.toArrayLike(Buffer, "le", 16),
+    report.observedFrom.toTwos(64).toArrayLike(Buffer, "le", 8),
+    report.observedTo.toTwos(64).toArrayLike(Buffer, "le", 8),
+  ]);
+}
+
+// Message the nodes sign to publish a report to `feed`, see `AnswerReport::signing_message`
+export function reportSigningMessage(feed: PublicKey, report: AnswerReport): Buffer {
+  return Buffer.concat([feed.toBuffer(), serializeAnswerReport(report)]);
+}
+
+// Zero-padded fixed-size strings of the zero-copy Feed account
+export function decodeFixedString(bytes: number[]): string {
+  const end = bytes.indexOf(0);
+  return Buffer.from(end === -1 ? bytes : bytes.slice(0, end)).toString("utf8");
+}
+
+// Offsets of the Feed hot fields in the account data (see `Feed::*_OFFSET`)
+export const FEED_OFFSETS = {
+  latestAnswer: 8,
+  balance: 88,
+  subscriptionDueTime: 96,
+  latestRoundId: 104,
+  minSignaturesThreshold: 112,
+  status: 113,
+};
+
+// PDA of a per-feed account seeded by `seed` and the feed address, e.g. "readers"
+export function getFeedAccountPda(programId: PublicKey, seed: string, feed: PublicKey) {
+  return PublicKey.findProgramAddressSync([Buffer.from(seed), feed.toBuffer()], programId);
+}
+
+export function getFeedHistoryPda(programId: PublicKey, feed: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("history"), feed.toBuffer()],
+    programId
+  );
+}
+
+// Decodes a FeedHistory account (zero-copy header + HistoryEntry entries),
+// returning the entries oldest first
+export async function fetchFeedHistory(ctx: TestContext, history: PublicKey) {
+  const info = await ctx.provider.connection.getAccountInfo(history);
+  const data = Buffer.from(info!.data);
+  const HEADER = 8 + 48;
+  const RECORD = 96;
+  const capacity = data.readUInt32LE(8 + 32);
+  const len = data.readUInt32LE(8 + 36);
+  const head = data.readUInt32LE(8 + 40);
+
+  const entries = [];
+  for (let i = 0; i < len; i++) {
+    const offset = HEADER + ((head + capacity - len + i) % capacity) * RECORD;
+    entries.push({
+      roundId: new BN(data.subarray(offset, offset + 8), "le"),
+      observedFrom: new BN(data.subarray(offset + 8, offset + 16), "le"),
+      observedTo: new BN(data.subarray(offset + 16, offset + 24), "le"),
+      value: Array.from(data.subarray(offset + 24, offset + 56)),
+      confidence: new BN(data.subarray(offset + 56, offset + 72), "le"),
+      exponent: data.readInt32LE(offset + 72),
+      signerCount: data.readUInt8(offset + 76),
+      priceCumulative: new BN(data.subarray(offset + 80, offset + 96), "le").fromTwos(128),
+    });
+  }
+  return { capacity, len, entries, space: data.length };
+}
+
+// Accounts reimbursing the provider wallet as the transmitter of published answers
+export function transmitterAccounts(ctx: TestContext) {
+  return {
+    transmitter: ctx.authority.publicKey,
+    transmitterTokenAccount: ctx.userTokenAccount,
+    programTokenAccount: ctx.programTokenAccount,
+    protocolConfig: ctx.protocolConfigPDA,
+    underlyingToken: ctx.underlyingTokenMint,
+    tokenProgram: TOKEN_PROGRAM_ID,
+  };
+}
+
+// Publishes a report to a feed, signed by the given nodes
+export async function publishTestAnswer(
+  ctx: TestContext,
+  feed: PublicKey,
+  report: AnswerReport,
+  signers: Keypair[] = ctx.nodes.slice(0, 2),
+  history: PublicKey | null = null
+) {
+  const message = reportSigningMessage(feed, report);
+  const preIxs = signers.map((signer) =>
+    anchor.web3.Ed25519Program.createInstructionWithPublicKey({
+      publicKey: signer.publicKey.toBytes(),
+      message,
+      signature: nacl.sign.detached(message, signer.secretKey),
+    })
+  );
+  await ctx.molphaProgram.methods
+    .publishAnswer(report)
+    .accountsPartial({
+      feed,
+      history,
+      nodeRegistry: ctx.nodeRegistryPDA,
+      instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+      ...transmitterAccounts(ctx),
+    })
+    .preInstructions(preIxs)
+    .rpc();
+}
+
+// Merkle tree over (feed, report) leaves, matching utils/merkle.rs
+export function leafHash(feed: PublicKey, report: AnswerReport): Buffer {
+  return createHash("sha256")
+    .update(Buffer.from([0]))
+    .update(feed.toBuffer())
+    .update(serializeAnswerReport(report))
+    .digest();
+}
+
+function nodeHash(a: Buffer, b: Buffer): Buffer {
+  const [left, right] = Buffer.compare(a, b) <= 0 ? [a, b] : [b, a];
+  return createHash("sha256")
+    .update(Buffer.from([1]))
+    .update(left)
+    .update(right)
+    .digest();
+}
+
+// Builds the root and one sibling path per leaf. Odd nodes are promoted as is.
+export function buildMerkleTree(leaves: Buffer[]): { root: Buffer; proofs: Buffer[][] } {
+  const proofs: Buffer[][] = leaves.map(() => []);
+  let positions = leaves.map((_, i) => i);
+  let level = leaves;
+
+  while (level.length > 1) {
+    const next: Buffer[] = [];
+    for (let i = 0; i < level.length; i += 2) {
+      next.push(i + 1 < level.length ? nodeHash(level[i], level[i + 1]) : level[i]);
+    }
+    positions = positions.map((pos, leaf) => {
+      const sibling = pos ^ 1;
+      if (sibling < level.length) proofs[leaf].push(level[sibling]);
+      return pos >> 1;
+    });
+    level = next;
+  }
+
+  return { root: level[0], proofs };
+}